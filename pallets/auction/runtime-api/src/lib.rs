@@ -0,0 +1,18 @@
+//! Runtime API through which UIs read the current price of Dutch auctions.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+
+sp_api::decl_runtime_apis! {
+	pub trait AuctionApi<CollectionId, NftId, Balance>
+	where
+		CollectionId: Codec,
+		NftId: Codec,
+		Balance: Codec,
+	{
+		/// Returns the current price of the Dutch auction of an NFT, or of the drop of a
+		/// collection if `nft_id` is `None`.
+		fn dutch_auction_price(collection_id: CollectionId, nft_id: Option<NftId>) -> Option<Balance>;
+	}
+}
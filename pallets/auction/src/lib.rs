@@ -0,0 +1,690 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::{
+	ensure,
+	traits::{Currency, ExistenceRequirement, ReservableCurrency},
+	transactional, BoundedVec,
+};
+use frame_system::ensure_signed;
+
+use pallet_nft_core::{
+	traits::{NftLock, NftMint, RoyaltyHandler},
+	types::AccountIdOrCollectionNftTuple,
+	BalanceOf,
+};
+use sp_runtime::{
+	helpers_128bit::multiply_by_rational,
+	traits::{One, SaturatedConversion, Saturating, Zero},
+	DispatchError,
+};
+
+use types::{AuctionInfo, DropInfo, DutchAuctionInfo, PriceDecay};
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+pub mod types;
+
+pub type AuctionInfoOf<T> = AuctionInfo<
+	<T as frame_system::Config>::AccountId,
+	BalanceOf<T>,
+	<T as frame_system::Config>::BlockNumber,
+>;
+
+pub type DutchAuctionInfoOf<T> = DutchAuctionInfo<
+	<T as frame_system::Config>::AccountId,
+	BalanceOf<T>,
+	<T as frame_system::Config>::BlockNumber,
+	BoundedVec<u8, <T as pallet_uniques::Config>::StringLimit>,
+>;
+pub type PriceDecayOf<T> = PriceDecay<BalanceOf<T>, <T as frame_system::Config>::BlockNumber>;
+
+/// The number of blocks searched for space when an auction is extended into a block in which
+/// `MaxAuctionsEndingPerBlock` auctions already end.
+pub const MAX_EXTENSION_SEARCH: u32 = 10;
+
+// Re-export pallet items so that they can be accessed from the crate namespace.
+pub use pallet::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+
+	use super::*;
+	use frame_support::{dispatch::DispatchResult, pallet_prelude::*};
+	use frame_system::pallet_prelude::*;
+
+	/// Configure the pallet by specifying the parameters and types on which it depends.
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_nft_core::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The number of blocks before the end of an auction in which a bid extends the auction,
+		/// so that it ends this many blocks after the bid.
+		#[pallet::constant]
+		type AntiSnipingPeriod: Get<Self::BlockNumber>;
+
+		/// The maximum number of auctions ending in the same block.
+		#[pallet::constant]
+		type MaxAuctionsEndingPerBlock: Get<u32>;
+	}
+
+	#[pallet::storage]
+	#[pallet::getter(fn auctions)]
+	/// Stores the running auctions of nfts
+	pub type Auctions<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::CollectionId,
+		Twox64Concat,
+		T::NftId,
+		AuctionInfoOf<T>,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn auction_endings)]
+	/// Stores the auctions ending at each block, which are settled when the block is finalized
+	pub type AuctionEndings<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		BoundedVec<(T::CollectionId, T::NftId), T::MaxAuctionsEndingPerBlock>,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn dutch_auctions)]
+	/// Stores the Dutch auctions of nfts, and of collection drops under a `None` nft id
+	pub type DutchAuctions<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::CollectionId,
+		Twox64Concat,
+		Option<T::NftId>,
+		DutchAuctionInfoOf<T>,
+	>;
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		AuctionCreated(T::AccountId, T::CollectionId, T::NftId, BalanceOf<T>, T::BlockNumber),
+		AuctionCancelled(T::CollectionId, T::NftId),
+		BidPlaced(T::AccountId, T::CollectionId, T::NftId, BalanceOf<T>),
+		AuctionExtended(T::CollectionId, T::NftId, T::BlockNumber),
+		AuctionSettled(T::CollectionId, T::NftId, Option<(T::AccountId, BalanceOf<T>)>),
+		AuctionSettlementFailed(T::CollectionId, T::NftId, DispatchError),
+		DutchAuctionCreated(
+			T::AccountId,
+			T::CollectionId,
+			Option<T::NftId>,
+			BalanceOf<T>,
+			BalanceOf<T>,
+		),
+		DutchAuctionCancelled(T::CollectionId, Option<T::NftId>),
+		DutchAuctionSold(T::AccountId, T::AccountId, T::CollectionId, T::NftId, BalanceOf<T>),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		NoPermission,
+		NonTransferable,
+		AuctionUnknown,
+		AuctionEnded,
+		EndInPast,
+		TooManyAuctionsEnding,
+		CannotBidOnOwnNft,
+		BidTooLow,
+		AuctionHasBids,
+		AuctionExists,
+		FloorAboveStartPrice,
+		InvalidDecay,
+		NoSupply,
+		PriceTooHigh,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+			// Account for the auctions settled in `on_finalize`, no auction can be moved to end
+			// in the current block once it has started.
+			let ending = Self::auction_endings(now).map_or(0, |ending| ending.len() as u64);
+			T::DbWeight::get().reads_writes(
+				1 + ending *
+					(T::MaxNestingDepth::get() as u64 + 6 * T::MaxChildren::get() as u64 + 8),
+				1 + ending *
+					(T::MaxRoyaltyRecipients::get() as u64 +
+						3 * T::MaxChildren::get() as u64 +
+						10),
+			)
+		}
+
+		fn on_finalize(now: BlockNumberFor<T>) {
+			for (collection_id, nft_id) in AuctionEndings::<T>::take(now).unwrap_or_default() {
+				if let Some(auction) = Auctions::<T>::take(collection_id, nft_id) {
+					Self::settle(collection_id, nft_id, auction);
+				}
+			}
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Starts an English auction of an NFT
+		///
+		/// The NFT is locked in nft-core until the auction is settled or cancelled. The auction is
+		/// settled automatically at the end of its `end` block.
+		///
+		/// Parameters:
+		/// - `collection_id`: The collection of the NFT.
+		/// - `nft_id`: The NFT to be auctioned, the sender must be its root owner.
+		/// - `reserve`: The lowest bid accepted, including the royalty of the NFT.
+		/// - `min_increment`: The amount by which a bid must exceed the highest bid.
+		/// - `end`: The last block in which bids are accepted, after the current block.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(
+			T::MaxNestingDepth::get() as u64 + 5,
+			3,
+		))]
+		#[transactional]
+		pub fn create_auction(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			nft_id: T::NftId,
+			reserve: BalanceOf<T>,
+			min_increment: BalanceOf<T>,
+			end: T::BlockNumber,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(end > frame_system::Pallet::<T>::block_number(), Error::<T>::EndInPast);
+			let nft = pallet_nft_core::Pallet::<T>::nfts(collection_id, nft_id)
+				.ok_or(pallet_nft_core::Error::<T>::NftUnknown)?;
+			ensure!(nft.transferable, Error::<T>::NonTransferable);
+			ensure!(
+				sender == pallet_nft_core::Pallet::<T>::root_owner(collection_id, nft_id)?,
+				Error::<T>::NoPermission
+			);
+			pallet_nft_core::Pallet::<T>::lock(collection_id, nft_id)?;
+			Self::add_ending(collection_id, nft_id, end)?;
+			Auctions::<T>::insert(
+				collection_id,
+				nft_id,
+				AuctionInfo {
+					seller: sender.clone(),
+					reserve,
+					min_increment,
+					end,
+					highest_bid: None,
+				},
+			);
+
+			Self::deposit_event(Event::AuctionCreated(sender, collection_id, nft_id, reserve, end));
+			Ok(())
+		}
+
+		/// Cancels an auction without bids and unlocks the NFT
+		///
+		/// Parameters:
+		/// - `collection_id`: The collection of the NFT.
+		/// - `nft_id`: The auctioned NFT, the sender must be its seller.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 3))]
+		#[transactional]
+		pub fn cancel_auction(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			nft_id: T::NftId,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let auction =
+				Self::auctions(collection_id, nft_id).ok_or(Error::<T>::AuctionUnknown)?;
+			ensure!(sender == auction.seller, Error::<T>::NoPermission);
+			ensure!(auction.highest_bid.is_none(), Error::<T>::AuctionHasBids);
+			Auctions::<T>::remove(collection_id, nft_id);
+			Self::remove_ending(collection_id, nft_id, auction.end);
+			pallet_nft_core::Pallet::<T>::unlock(collection_id, nft_id);
+
+			Self::deposit_event(Event::AuctionCancelled(collection_id, nft_id));
+			Ok(())
+		}
+
+		/// Bids on an auction
+		///
+		/// The bid is reserved from the sender and the bid it outbids is unreserved. A bid within
+		/// `AntiSnipingPeriod` blocks of the end of the auction extends it to end
+		/// `AntiSnipingPeriod` blocks after the bid, or in the first block after that in which
+		/// fewer than `MaxAuctionsEndingPerBlock` auctions end.
+		///
+		/// Parameters:
+		/// - `collection_id`: The collection of the NFT.
+		/// - `nft_id`: The auctioned NFT.
+		/// - `amount`: The bid, at least the reserve of the auction and the highest bid plus the
+		///   minimum increment.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(
+			MAX_EXTENSION_SEARCH as u64 + 3,
+			6,
+		))]
+		#[transactional]
+		pub fn bid(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			nft_id: T::NftId,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let mut auction =
+				Self::auctions(collection_id, nft_id).ok_or(Error::<T>::AuctionUnknown)?;
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(now <= auction.end, Error::<T>::AuctionEnded);
+			ensure!(sender != auction.seller, Error::<T>::CannotBidOnOwnNft);
+			ensure!(amount >= auction.reserve, Error::<T>::BidTooLow);
+			if let Some((bidder, highest)) = &auction.highest_bid {
+				ensure!(
+					amount >= highest.saturating_add(auction.min_increment),
+					Error::<T>::BidTooLow
+				);
+				<T as pallet_nft_core::Config>::Currency::unreserve(bidder, *highest);
+			}
+			<T as pallet_nft_core::Config>::Currency::reserve(&sender, amount)?;
+			auction.highest_bid = Some((sender.clone(), amount));
+
+			let extended_end = now.saturating_add(T::AntiSnipingPeriod::get());
+			if extended_end > auction.end {
+				Self::remove_ending(collection_id, nft_id, auction.end);
+				let extended_end = Self::add_extended_ending(collection_id, nft_id, extended_end)?;
+				auction.end = extended_end;
+				Self::deposit_event(Event::AuctionExtended(collection_id, nft_id, extended_end));
+			}
+			Auctions::<T>::insert(collection_id, nft_id, auction);
+
+			Self::deposit_event(Event::BidPlaced(sender, collection_id, nft_id, amount));
+			Ok(())
+		}
+
+		/// Starts a Dutch auction of an NFT
+		///
+		/// The price decays from `start_price` to `floor_price` from the current block on and
+		/// the first buyer at the current price wins. The NFT is locked in nft-core until it is
+		/// sold or the auction is cancelled.
+		///
+		/// Parameters:
+		/// - `collection_id`: The collection of the NFT.
+		/// - `nft_id`: The NFT to be auctioned, the sender must be its root owner.
+		/// - `start_price`: The price at the start of the auction, including the royalty of the
+		///   NFT.
+		/// - `floor_price`: The price below which the auction does not decay.
+		/// - `decay`: How the price decays, its duration or interval must not be zero.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(
+			T::MaxNestingDepth::get() as u64 + 4,
+			2,
+		))]
+		#[transactional]
+		pub fn create_dutch_auction(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			nft_id: T::NftId,
+			start_price: BalanceOf<T>,
+			floor_price: BalanceOf<T>,
+			decay: PriceDecayOf<T>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let nft = pallet_nft_core::Pallet::<T>::nfts(collection_id, nft_id)
+				.ok_or(pallet_nft_core::Error::<T>::NftUnknown)?;
+			ensure!(nft.transferable, Error::<T>::NonTransferable);
+			ensure!(
+				sender == pallet_nft_core::Pallet::<T>::root_owner(collection_id, nft_id)?,
+				Error::<T>::NoPermission
+			);
+			pallet_nft_core::Pallet::<T>::lock(collection_id, nft_id)?;
+			Self::do_create_dutch_auction(
+				sender,
+				collection_id,
+				Some(nft_id),
+				start_price,
+				floor_price,
+				decay,
+				None,
+			)
+		}
+
+		/// Starts a Dutch auction of a collection drop, minting an NFT to each buyer
+		///
+		/// The price decays from `start_price` to `floor_price` from the current block on. Each
+		/// buyer pays the current price to the sender and the instance and metadata deposits of
+		/// their NFT, which is authored by the sender without a royalty.
+		///
+		/// Parameters:
+		/// - `collection_id`: The collection of the drop, the sender must be its issuer.
+		/// - `supply`: The number of NFTs to be sold.
+		/// - `metadata`: The metadata of the minted NFTs.
+		/// - `start_price`: The price at the start of the auction.
+		/// - `floor_price`: The price below which the auction does not decay.
+		/// - `decay`: How the price decays, its duration or interval must not be zero.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 1))]
+		#[transactional]
+		pub fn create_drop(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			supply: u32,
+			metadata: BoundedVec<u8, <T as pallet_uniques::Config>::StringLimit>,
+			start_price: BalanceOf<T>,
+			floor_price: BalanceOf<T>,
+			decay: PriceDecayOf<T>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(
+				sender == pallet_nft_core::Pallet::<T>::collection_issuer(collection_id)?,
+				Error::<T>::NoPermission
+			);
+			ensure!(supply > 0, Error::<T>::NoSupply);
+			Self::do_create_dutch_auction(
+				sender,
+				collection_id,
+				None,
+				start_price,
+				floor_price,
+				decay,
+				Some(DropInfo { remaining: supply, metadata }),
+			)
+		}
+
+		/// Cancels a Dutch auction, unlocking the NFT or ending the drop
+		///
+		/// Parameters:
+		/// - `collection_id`: The collection of the auction.
+		/// - `nft_id`: The auctioned NFT, or `None` for a collection drop. The sender must be the
+		///   seller of the auction.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 2))]
+		#[transactional]
+		pub fn cancel_dutch_auction(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			nft_id: Option<T::NftId>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let auction =
+				Self::dutch_auctions(collection_id, nft_id).ok_or(Error::<T>::AuctionUnknown)?;
+			ensure!(sender == auction.seller, Error::<T>::NoPermission);
+			DutchAuctions::<T>::remove(collection_id, nft_id);
+			if let Some(nft_id) = nft_id {
+				pallet_nft_core::Pallet::<T>::unlock(collection_id, nft_id);
+			}
+
+			Self::deposit_event(Event::DutchAuctionCancelled(collection_id, nft_id));
+			Ok(())
+		}
+
+		/// Buys from a Dutch auction at its current price
+		///
+		/// The NFT of the auction is sent to the sender, paying its royalty from the price, or
+		/// an NFT of the drop is minted to the sender.
+		///
+		/// Parameters:
+		/// - `collection_id`: The collection of the auction.
+		/// - `nft_id`: The auctioned NFT, or `None` for a collection drop.
+		/// - `max_price`: The highest price the sender is willing to pay.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(
+			T::MaxNestingDepth::get() as u64 +
+				T::MaxRoyaltyRecipients::get() as u64 +
+				6 * T::MaxChildren::get() as u64 +
+				8,
+			T::MaxRoyaltyRecipients::get() as u64 + 3 * T::MaxChildren::get() as u64 + 8,
+		))]
+		#[transactional]
+		pub fn buy_dutch(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			nft_id: Option<T::NftId>,
+			max_price: BalanceOf<T>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let mut auction =
+				Self::dutch_auctions(collection_id, nft_id).ok_or(Error::<T>::AuctionUnknown)?;
+			ensure!(sender != auction.seller, Error::<T>::CannotBidOnOwnNft);
+			let price = Self::current_price(&auction, frame_system::Pallet::<T>::block_number());
+			ensure!(price <= max_price, Error::<T>::PriceTooHigh);
+
+			let bought_nft_id = match (nft_id, auction.drop.as_mut()) {
+				(Some(nft_id), _) => {
+					DutchAuctions::<T>::remove(collection_id, Some(nft_id));
+					pallet_nft_core::Pallet::<T>::unlock(collection_id, nft_id);
+					let proceeds = pallet_nft_core::Pallet::<T>::pay_royalty(
+						&sender,
+						collection_id,
+						nft_id,
+						price,
+					)?;
+					Self::pay(&sender, &auction.seller, proceeds)?;
+					pallet_nft_core::Pallet::<T>::do_send(
+						collection_id,
+						nft_id,
+						AccountIdOrCollectionNftTuple::AccountId(sender.clone()),
+						None,
+					)?;
+					nft_id
+				},
+				(None, Some(drop)) => {
+					Self::pay(&sender, &auction.seller, price)?;
+					let nft_id = pallet_nft_core::Pallet::<T>::mint(
+						sender.clone(),
+						collection_id,
+						drop.metadata.clone(),
+					)?;
+					drop.remaining = drop.remaining.saturating_sub(1);
+					if drop.remaining == 0 {
+						DutchAuctions::<T>::remove(collection_id, None::<T::NftId>);
+					} else {
+						DutchAuctions::<T>::insert(collection_id, None::<T::NftId>, &auction);
+					}
+					nft_id
+				},
+				(None, None) => return Err(Error::<T>::AuctionUnknown.into()),
+			};
+
+			Self::deposit_event(Event::DutchAuctionSold(
+				auction.seller,
+				sender,
+				collection_id,
+				bought_nft_id,
+				price,
+			));
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Returns the current price of the Dutch auction of an NFT, or of the drop of a
+		/// collection if `nft_id` is `None`.
+		pub fn dutch_auction_price(
+			collection_id: T::CollectionId,
+			nft_id: Option<T::NftId>,
+		) -> Option<BalanceOf<T>> {
+			Self::dutch_auctions(collection_id, nft_id).map(|auction| {
+				Self::current_price(&auction, frame_system::Pallet::<T>::block_number())
+			})
+		}
+
+		/// Returns the price of a Dutch auction at block `now`.
+		pub fn current_price(auction: &DutchAuctionInfoOf<T>, now: T::BlockNumber) -> BalanceOf<T> {
+			let elapsed = now.saturating_sub(auction.start);
+			let range = auction.start_price.saturating_sub(auction.floor_price);
+			let decayed = match auction.decay {
+				PriceDecay::Linear { duration } if elapsed < duration => multiply_by_rational(
+					range.saturated_into(),
+					elapsed.saturated_into(),
+					duration.saturated_into(),
+				)
+				.map(|decayed| decayed.saturated_into())
+				.unwrap_or(range),
+				PriceDecay::Linear { .. } => range,
+				PriceDecay::Stepwise { step, interval } => {
+					let steps: u128 = (elapsed / interval).saturated_into();
+					step.saturating_mul(steps.saturated_into())
+				},
+			};
+			auction.start_price.saturating_sub(decayed).max(auction.floor_price)
+		}
+
+		fn do_create_dutch_auction(
+			seller: T::AccountId,
+			collection_id: T::CollectionId,
+			nft_id: Option<T::NftId>,
+			start_price: BalanceOf<T>,
+			floor_price: BalanceOf<T>,
+			decay: PriceDecayOf<T>,
+			drop: Option<DropInfo<BoundedVec<u8, <T as pallet_uniques::Config>::StringLimit>>>,
+		) -> DispatchResult {
+			ensure!(
+				!DutchAuctions::<T>::contains_key(collection_id, nft_id),
+				Error::<T>::AuctionExists
+			);
+			ensure!(floor_price <= start_price, Error::<T>::FloorAboveStartPrice);
+			let valid_decay = match decay {
+				PriceDecay::Linear { duration } => !duration.is_zero(),
+				PriceDecay::Stepwise { interval, .. } => !interval.is_zero(),
+			};
+			ensure!(valid_decay, Error::<T>::InvalidDecay);
+			DutchAuctions::<T>::insert(
+				collection_id,
+				nft_id,
+				DutchAuctionInfo {
+					seller: seller.clone(),
+					start_price,
+					floor_price,
+					start: frame_system::Pallet::<T>::block_number(),
+					decay,
+					drop,
+				},
+			);
+
+			Self::deposit_event(Event::DutchAuctionCreated(
+				seller,
+				collection_id,
+				nft_id,
+				start_price,
+				floor_price,
+			));
+			Ok(())
+		}
+
+		fn pay(from: &T::AccountId, to: &T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
+			if amount.is_zero() {
+				return Ok(())
+			}
+			<T as pallet_nft_core::Config>::Currency::transfer(
+				from,
+				to,
+				amount,
+				ExistenceRequirement::AllowDeath,
+			)
+		}
+
+		/// Settles an ended auction, falling back to refunding the highest bidder and unlocking
+		/// the NFT if the sale fails.
+		fn settle(collection_id: T::CollectionId, nft_id: T::NftId, auction: AuctionInfoOf<T>) {
+			pallet_nft_core::Pallet::<T>::unlock(collection_id, nft_id);
+			match Self::do_settle(collection_id, nft_id, &auction) {
+				Ok(()) => Self::deposit_event(Event::AuctionSettled(
+					collection_id,
+					nft_id,
+					auction.highest_bid,
+				)),
+				Err(error) => {
+					if let Some((bidder, amount)) = auction.highest_bid {
+						<T as pallet_nft_core::Config>::Currency::unreserve(&bidder, amount);
+					}
+					Self::deposit_event(Event::AuctionSettlementFailed(
+						collection_id,
+						nft_id,
+						error,
+					));
+				},
+			}
+		}
+
+		/// Pays the winning bid, with the royalty of the NFT, to the seller and sends the NFT to
+		/// the highest bidder.
+		#[transactional]
+		fn do_settle(
+			collection_id: T::CollectionId,
+			nft_id: T::NftId,
+			auction: &AuctionInfoOf<T>,
+		) -> DispatchResult {
+			let (bidder, amount) = match &auction.highest_bid {
+				Some(highest_bid) => highest_bid,
+				None => return Ok(()),
+			};
+			<T as pallet_nft_core::Config>::Currency::unreserve(bidder, *amount);
+			let proceeds =
+				pallet_nft_core::Pallet::<T>::pay_royalty(bidder, collection_id, nft_id, *amount)?;
+			if !proceeds.is_zero() {
+				<T as pallet_nft_core::Config>::Currency::transfer(
+					bidder,
+					&auction.seller,
+					proceeds,
+					ExistenceRequirement::AllowDeath,
+				)?;
+			}
+			pallet_nft_core::Pallet::<T>::do_send(
+				collection_id,
+				nft_id,
+				AccountIdOrCollectionNftTuple::AccountId(bidder.clone()),
+				None,
+			)?;
+			Ok(())
+		}
+
+		fn add_ending(
+			collection_id: T::CollectionId,
+			nft_id: T::NftId,
+			end: T::BlockNumber,
+		) -> DispatchResult {
+			AuctionEndings::<T>::try_mutate(end, |maybe_ending| -> DispatchResult {
+				maybe_ending
+					.get_or_insert_with(Default::default)
+					.try_push((collection_id, nft_id))
+					.map_err(|_| Error::<T>::TooManyAuctionsEnding.into())
+			})
+		}
+
+		/// Adds the auction to the endings of the first block from `end` on with space for it,
+		/// searching up to `MAX_EXTENSION_SEARCH` blocks. Returns the block the auction ends in.
+		fn add_extended_ending(
+			collection_id: T::CollectionId,
+			nft_id: T::NftId,
+			end: T::BlockNumber,
+		) -> Result<T::BlockNumber, DispatchError> {
+			let mut end = end;
+			for _ in 1..MAX_EXTENSION_SEARCH {
+				if Self::add_ending(collection_id, nft_id, end).is_ok() {
+					return Ok(end)
+				}
+				end = end.saturating_add(One::one());
+			}
+			Self::add_ending(collection_id, nft_id, end)?;
+			Ok(end)
+		}
+
+		fn remove_ending(collection_id: T::CollectionId, nft_id: T::NftId, end: T::BlockNumber) {
+			AuctionEndings::<T>::mutate_exists(end, |maybe_ending| {
+				if let Some(ending) = maybe_ending {
+					ending.retain(|entry| entry != &(collection_id, nft_id));
+					if ending.is_empty() {
+						*maybe_ending = None;
+					}
+				}
+			});
+		}
+	}
+}
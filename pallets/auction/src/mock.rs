@@ -0,0 +1,192 @@
+use super::*;
+use crate as pallet_auction;
+
+use frame_support::traits::Everything;
+use frame_support::{parameter_types, weights::Weight};
+use frame_system::EnsureRoot;
+use sp_core::{crypto::AccountId32, H256};
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+	MultiSignature, MultiSigner, Perbill,
+};
+
+type AccountId = AccountId32;
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+type Balance = u128;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Uniques: pallet_uniques::{Pallet, Storage, Event<T>},
+		NFTCore: pallet_nft_core::{Pallet, Call, Event<T>},
+		Auction: pallet_auction::{Pallet, Call, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+	}
+);
+
+parameter_types! {
+	pub ClassBondAmount: Balance = 100;
+	pub MaxMetadataLength: u32 = 256;
+	pub const MaxNestingDepth: u32 = 3;
+	pub const MaxChildren: u32 = 4;
+	pub const PartsLimit: u32 = 3;
+	pub const MaxCollectionsEquippablePerPart: u32 = 2;
+	pub const MaxPropertiesPerTheme: u32 = 3;
+	pub const MaxBatchMint: u32 = 5;
+	pub const MaxPriorities: u32 = 5;
+	pub const MaxRoyaltyRecipients: u32 = 3;
+	pub const MaxProofLength: u32 = 8;
+}
+
+impl pallet_nft_core::Config for Test {
+	type Currency = Balances;
+	type Event = Event;
+	type CollectionId = u32;
+	type NftId = u32;
+	type ResourceId = u32;
+	type ProtocolOrigin = EnsureRoot<AccountId>;
+	type MaxNestingDepth = MaxNestingDepth;
+	type MaxChildren = MaxChildren;
+	type PartsLimit = PartsLimit;
+	type MaxCollectionsEquippablePerPart = MaxCollectionsEquippablePerPart;
+	type MaxPropertiesPerTheme = MaxPropertiesPerTheme;
+	type MaxBatchMint = MaxBatchMint;
+	type WeightInfo = ();
+	type MaxPriorities = MaxPriorities;
+	type MaxRoyaltyRecipients = MaxRoyaltyRecipients;
+	type MaxProofLength = MaxProofLength;
+	type OffchainSignature = MultiSignature;
+	type OffchainPublic = MultiSigner;
+}
+
+parameter_types! {
+	pub const AntiSnipingPeriod: u64 = 5;
+	pub const MaxAuctionsEndingPerBlock: u32 = 2;
+}
+
+impl pallet_auction::Config for Test {
+	type Event = Event;
+	type AntiSnipingPeriod = AntiSnipingPeriod;
+	type MaxAuctionsEndingPerBlock = MaxAuctionsEndingPerBlock;
+}
+
+parameter_types! {
+	pub const ClassDeposit: Balance = 10_000 * RMRK; // 1 UNIT deposit to create asset class
+	pub const InstanceDeposit: Balance = 100 * RMRK; // 1/100 UNIT deposit to create asset instance
+	pub const KeyLimit: u32 = 32;	// Max 32 bytes per key
+	pub const ValueLimit: u32 = 64;	// Max 64 bytes per value
+	pub const UniquesMetadataDepositBase: Balance = 1000 * RMRK;
+	pub const AttributeDepositBase: Balance = 100 * RMRK;
+	pub const DepositPerByte: Balance = 10 * RMRK;
+	pub const UniquesStringLimit: u32 = 32;
+}
+
+impl pallet_uniques::Config for Test {
+	type Event = Event;
+	type ClassId = u32;
+	type InstanceId = u32;
+	type Currency = Balances;
+	type ForceOrigin = EnsureRoot<AccountId>;
+	type ClassDeposit = ClassDeposit;
+	type InstanceDeposit = InstanceDeposit;
+	type MetadataDepositBase = UniquesMetadataDepositBase;
+	type AttributeDepositBase = AttributeDepositBase;
+	type DepositPerByte = DepositPerByte;
+	type StringLimit = UniquesStringLimit;
+	type KeyLimit = KeyLimit;
+	type ValueLimit = ValueLimit;
+	type Locker = NFTCore;
+	type WeightInfo = ();
+	// type InstanceReserveStrategy = NFT;
+}
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const MaximumBlockWeight: Weight = 1024;
+	pub const MaximumBlockLength: u32 = 2 * 1024;
+	pub const AvailableBlockRatio: Perbill = Perbill::one();
+}
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<Balance>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+}
+
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+	pub const MaxReserves: u32 = 50;
+}
+impl pallet_balances::Config for Test {
+	type Balance = Balance;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = frame_system::Pallet<Test>;
+	type MaxLocks = ();
+	type WeightInfo = ();
+	type MaxReserves = MaxReserves;
+	// type ReserveIdentifier = ReserveIdentifier;
+	type ReserveIdentifier = ();
+}
+
+pub const ALICE: AccountId = AccountId::new([1u8; 32]);
+pub const BOB: AccountId = AccountId::new([2u8; 32]);
+pub const CHARLIE: AccountId = AccountId::new([3u8; 32]);
+pub const RMRK: Balance = 1;
+pub const COLLECTION_ID_0: <Test as pallet_uniques::Config>::ClassId = 0;
+pub const TOKEN_ID_0: <Test as pallet_uniques::Config>::InstanceId = 0;
+
+pub struct ExtBuilder;
+impl Default for ExtBuilder {
+	fn default() -> Self {
+		ExtBuilder
+	}
+}
+
+impl ExtBuilder {
+	pub fn build(self) -> sp_io::TestExternalities {
+		let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+
+		pallet_balances::GenesisConfig::<Test> {
+			balances: vec![
+				(ALICE, 200_000 * RMRK),
+				(BOB, 150_000 * RMRK),
+				(CHARLIE, 150_000 * RMRK),
+			],
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+}
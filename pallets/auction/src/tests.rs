@@ -0,0 +1,487 @@
+use frame_support::{assert_noop, assert_ok, traits::Hooks};
+
+use super::*;
+use mock::*;
+use pallet_nft_core::types::AccountIdOrCollectionNftTuple::AccountId as ToAccount;
+use sp_runtime::Permill;
+
+type NFTCore = pallet_nft_core::Pallet<Test>;
+
+/// Turns a string into a Vec
+fn stv(s: &str) -> Vec<u8> {
+	s.as_bytes().to_vec()
+}
+
+/// Creates a collection owned by ALICE and mints an NFT authored by ALICE with a 10% royalty
+/// to BOB
+fn basic_mint() {
+	assert_ok!(NFTCore::create_collection(
+		Origin::signed(ALICE),
+		stv("metadata"),
+		None,
+		stv("SYM"),
+		None
+	));
+	assert_ok!(NFTCore::mint_nft(
+		Origin::signed(ALICE),
+		COLLECTION_ID_0,
+		Some(BOB),
+		Some(ALICE),
+		Some(Permill::from_percent(10)),
+		None,
+		Some(stv("metadata")),
+		true
+	));
+}
+
+/// Starts an auction of the NFT of BOB with a reserve of 1_000 ending at block 20
+fn basic_auction() {
+	assert_ok!(Auction::create_auction(
+		Origin::signed(BOB),
+		COLLECTION_ID_0,
+		TOKEN_ID_0,
+		1_000,
+		100,
+		20
+	));
+}
+
+/// Runs the hooks of the auction pallet up to and including block `n`
+fn run_to_block(n: u64) {
+	while System::block_number() < n {
+		Auction::on_finalize(System::block_number());
+		System::set_block_number(System::block_number() + 1);
+		Auction::on_initialize(System::block_number());
+	}
+	Auction::on_finalize(n);
+}
+
+#[test]
+fn create_auction_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_mint();
+		assert_noop!(
+			Auction::create_auction(
+				Origin::signed(BOB),
+				COLLECTION_ID_0,
+				TOKEN_ID_0,
+				1_000,
+				100,
+				1
+			),
+			Error::<Test>::EndInPast
+		);
+		assert_noop!(
+			Auction::create_auction(
+				Origin::signed(ALICE),
+				COLLECTION_ID_0,
+				TOKEN_ID_0,
+				1_000,
+				100,
+				20
+			),
+			Error::<Test>::NoPermission
+		);
+		basic_auction();
+		System::assert_last_event(mock::Event::Auction(crate::Event::AuctionCreated(
+			BOB,
+			COLLECTION_ID_0,
+			TOKEN_ID_0,
+			1_000,
+			20,
+		)));
+		assert_eq!(
+			Auction::auctions(COLLECTION_ID_0, TOKEN_ID_0),
+			Some(AuctionInfo {
+				seller: BOB,
+				reserve: 1_000,
+				min_increment: 100,
+				end: 20,
+				highest_bid: None
+			})
+		);
+		assert_eq!(Auction::auction_endings(20).unwrap().into_inner(), vec![(0, 0)]);
+		// The auctioned NFT is locked in nft-core
+		assert_noop!(
+			NFTCore::send(Origin::signed(BOB), COLLECTION_ID_0, TOKEN_ID_0, ToAccount(CHARLIE)),
+			pallet_nft_core::Error::<Test>::NftLocked
+		);
+	});
+}
+
+#[test]
+fn cancel_auction_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_mint();
+		basic_auction();
+		assert_noop!(
+			Auction::cancel_auction(Origin::signed(CHARLIE), COLLECTION_ID_0, TOKEN_ID_0),
+			Error::<Test>::NoPermission
+		);
+		assert_ok!(Auction::cancel_auction(Origin::signed(BOB), COLLECTION_ID_0, TOKEN_ID_0));
+		System::assert_last_event(mock::Event::Auction(crate::Event::AuctionCancelled(
+			COLLECTION_ID_0,
+			TOKEN_ID_0,
+		)));
+		assert_eq!(Auction::auctions(COLLECTION_ID_0, TOKEN_ID_0), None);
+		assert_eq!(Auction::auction_endings(20), None);
+		assert!(!pallet_nft_core::Locks::<Test>::contains_key(COLLECTION_ID_0, TOKEN_ID_0));
+		// An auction with bids cannot be cancelled
+		basic_auction();
+		assert_ok!(Auction::bid(Origin::signed(CHARLIE), COLLECTION_ID_0, TOKEN_ID_0, 1_000));
+		assert_noop!(
+			Auction::cancel_auction(Origin::signed(BOB), COLLECTION_ID_0, TOKEN_ID_0),
+			Error::<Test>::AuctionHasBids
+		);
+	});
+}
+
+#[test]
+fn bid_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_mint();
+		assert_noop!(
+			Auction::bid(Origin::signed(CHARLIE), COLLECTION_ID_0, TOKEN_ID_0, 1_000),
+			Error::<Test>::AuctionUnknown
+		);
+		basic_auction();
+		assert_noop!(
+			Auction::bid(Origin::signed(BOB), COLLECTION_ID_0, TOKEN_ID_0, 1_000),
+			Error::<Test>::CannotBidOnOwnNft
+		);
+		assert_noop!(
+			Auction::bid(Origin::signed(CHARLIE), COLLECTION_ID_0, TOKEN_ID_0, 999),
+			Error::<Test>::BidTooLow
+		);
+		assert_ok!(Auction::bid(Origin::signed(CHARLIE), COLLECTION_ID_0, TOKEN_ID_0, 1_000));
+		System::assert_last_event(mock::Event::Auction(crate::Event::BidPlaced(
+			CHARLIE,
+			COLLECTION_ID_0,
+			TOKEN_ID_0,
+			1_000,
+		)));
+		assert_eq!(Balances::reserved_balance(&CHARLIE), 1_000);
+		// A bid must exceed the highest bid by the minimum increment
+		assert_noop!(
+			Auction::bid(Origin::signed(ALICE), COLLECTION_ID_0, TOKEN_ID_0, 1_099),
+			Error::<Test>::BidTooLow
+		);
+		let alice_reserved = Balances::reserved_balance(&ALICE);
+		assert_ok!(Auction::bid(Origin::signed(ALICE), COLLECTION_ID_0, TOKEN_ID_0, 1_100));
+		// The outbid bidder is refunded
+		assert_eq!(Balances::reserved_balance(&CHARLIE), 0);
+		assert_eq!(Balances::reserved_balance(&ALICE), alice_reserved + 1_100);
+		assert_eq!(
+			Auction::auctions(COLLECTION_ID_0, TOKEN_ID_0).unwrap().highest_bid,
+			Some((ALICE, 1_100))
+		);
+		System::set_block_number(21);
+		assert_noop!(
+			Auction::bid(Origin::signed(CHARLIE), COLLECTION_ID_0, TOKEN_ID_0, 2_000),
+			Error::<Test>::AuctionEnded
+		);
+	});
+}
+
+#[test]
+fn late_bid_extends_auction() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_mint();
+		basic_auction();
+		// A bid before the anti-sniping period does not extend the auction
+		System::set_block_number(15);
+		assert_ok!(Auction::bid(Origin::signed(CHARLIE), COLLECTION_ID_0, TOKEN_ID_0, 1_000));
+		assert_eq!(Auction::auctions(COLLECTION_ID_0, TOKEN_ID_0).unwrap().end, 20);
+		System::set_block_number(18);
+		assert_ok!(Auction::bid(Origin::signed(ALICE), COLLECTION_ID_0, TOKEN_ID_0, 1_100));
+		System::assert_has_event(mock::Event::Auction(crate::Event::AuctionExtended(
+			COLLECTION_ID_0,
+			TOKEN_ID_0,
+			23,
+		)));
+		assert_eq!(Auction::auctions(COLLECTION_ID_0, TOKEN_ID_0).unwrap().end, 23);
+		assert_eq!(Auction::auction_endings(20), None);
+		assert_eq!(Auction::auction_endings(23).unwrap().into_inner(), vec![(0, 0)]);
+		// The auction is not settled at its original end
+		run_to_block(20);
+		assert!(Auction::auctions(COLLECTION_ID_0, TOKEN_ID_0).is_some());
+		assert_ok!(Auction::bid(Origin::signed(CHARLIE), COLLECTION_ID_0, TOKEN_ID_0, 1_200));
+		run_to_block(25);
+		assert_eq!(NFTCore::root_owner(COLLECTION_ID_0, TOKEN_ID_0).unwrap(), CHARLIE);
+	});
+}
+
+#[test]
+fn late_bid_extends_auction_past_full_blocks() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_mint();
+		basic_auction();
+		// The block the auction would be extended to has no space left
+		let ending: BoundedVec<(u32, u32), MaxAuctionsEndingPerBlock> =
+			vec![(COLLECTION_ID_0, 5), (COLLECTION_ID_0, 6)].try_into().unwrap();
+		AuctionEndings::<Test>::insert(23, ending);
+		System::set_block_number(18);
+		assert_ok!(Auction::bid(Origin::signed(CHARLIE), COLLECTION_ID_0, TOKEN_ID_0, 1_000));
+		System::assert_has_event(mock::Event::Auction(crate::Event::AuctionExtended(
+			COLLECTION_ID_0,
+			TOKEN_ID_0,
+			24,
+		)));
+		assert_eq!(Auction::auctions(COLLECTION_ID_0, TOKEN_ID_0).unwrap().end, 24);
+		assert_eq!(Auction::auction_endings(20), None);
+		assert_eq!(Auction::auction_endings(23).unwrap().len(), 2);
+		assert_eq!(Auction::auction_endings(24).unwrap().into_inner(), vec![(0, 0)]);
+		run_to_block(24);
+		assert_eq!(NFTCore::root_owner(COLLECTION_ID_0, TOKEN_ID_0).unwrap(), CHARLIE);
+	});
+}
+
+#[test]
+fn auction_settles_on_finalize() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_mint();
+		basic_auction();
+		assert_ok!(Auction::bid(Origin::signed(CHARLIE), COLLECTION_ID_0, TOKEN_ID_0, 2_000));
+
+		let alice_balance = Balances::free_balance(ALICE);
+		let bob_balance = Balances::free_balance(BOB);
+		let charlie_balance = Balances::free_balance(CHARLIE);
+		run_to_block(20);
+		System::assert_last_event(mock::Event::Auction(crate::Event::AuctionSettled(
+			COLLECTION_ID_0,
+			TOKEN_ID_0,
+			Some((CHARLIE, 2_000)),
+		)));
+		// The author receives the royalty and the seller the rest of the winning bid
+		assert_eq!(Balances::free_balance(ALICE), alice_balance + 200);
+		assert_eq!(Balances::free_balance(BOB), bob_balance + 1_800);
+		assert_eq!(Balances::free_balance(CHARLIE), charlie_balance);
+		assert_eq!(Balances::reserved_balance(&CHARLIE), 0);
+		assert_eq!(NFTCore::root_owner(COLLECTION_ID_0, TOKEN_ID_0).unwrap(), CHARLIE);
+		assert_eq!(Auction::auctions(COLLECTION_ID_0, TOKEN_ID_0), None);
+		assert_eq!(Auction::auction_endings(20), None);
+		assert!(!pallet_nft_core::Locks::<Test>::contains_key(COLLECTION_ID_0, TOKEN_ID_0));
+	});
+}
+
+#[test]
+fn auction_without_bids_returns_nft() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_mint();
+		basic_auction();
+		run_to_block(20);
+		System::assert_last_event(mock::Event::Auction(crate::Event::AuctionSettled(
+			COLLECTION_ID_0,
+			TOKEN_ID_0,
+			None,
+		)));
+		assert_eq!(NFTCore::root_owner(COLLECTION_ID_0, TOKEN_ID_0).unwrap(), BOB);
+		assert!(!pallet_nft_core::Locks::<Test>::contains_key(COLLECTION_ID_0, TOKEN_ID_0));
+	});
+}
+
+#[test]
+fn dutch_auction_price_decays() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_mint();
+		assert_noop!(
+			Auction::create_dutch_auction(
+				Origin::signed(BOB),
+				COLLECTION_ID_0,
+				TOKEN_ID_0,
+				1_000,
+				1_001,
+				PriceDecay::Linear { duration: 10 }
+			),
+			Error::<Test>::FloorAboveStartPrice
+		);
+		assert_noop!(
+			Auction::create_dutch_auction(
+				Origin::signed(BOB),
+				COLLECTION_ID_0,
+				TOKEN_ID_0,
+				1_000,
+				500,
+				PriceDecay::Stepwise { step: 100, interval: 0 }
+			),
+			Error::<Test>::InvalidDecay
+		);
+		assert_ok!(Auction::create_dutch_auction(
+			Origin::signed(BOB),
+			COLLECTION_ID_0,
+			TOKEN_ID_0,
+			1_000,
+			500,
+			PriceDecay::Linear { duration: 10 }
+		));
+		assert_eq!(Auction::dutch_auction_price(COLLECTION_ID_0, Some(TOKEN_ID_0)), Some(1_000));
+		System::set_block_number(6);
+		assert_eq!(Auction::dutch_auction_price(COLLECTION_ID_0, Some(TOKEN_ID_0)), Some(750));
+		System::set_block_number(30);
+		assert_eq!(Auction::dutch_auction_price(COLLECTION_ID_0, Some(TOKEN_ID_0)), Some(500));
+
+		let auction = DutchAuctionInfo {
+			seller: BOB,
+			start_price: 1_000,
+			floor_price: 500,
+			start: 1,
+			decay: PriceDecay::Stepwise { step: 200, interval: 3 },
+			drop: None,
+		};
+		assert_eq!(Auction::current_price(&auction, 3), 1_000);
+		assert_eq!(Auction::current_price(&auction, 4), 800);
+		assert_eq!(Auction::current_price(&auction, 7), 600);
+		assert_eq!(Auction::current_price(&auction, 10), 500);
+		assert_eq!(Auction::dutch_auction_price(COLLECTION_ID_0, None), None);
+	});
+}
+
+#[test]
+fn buy_dutch_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_mint();
+		assert_ok!(Auction::create_dutch_auction(
+			Origin::signed(BOB),
+			COLLECTION_ID_0,
+			TOKEN_ID_0,
+			2_000,
+			1_000,
+			PriceDecay::Linear { duration: 10 }
+		));
+		// The auctioned NFT is locked in nft-core
+		assert_noop!(
+			NFTCore::send(Origin::signed(BOB), COLLECTION_ID_0, TOKEN_ID_0, ToAccount(CHARLIE)),
+			pallet_nft_core::Error::<Test>::NftLocked
+		);
+		System::set_block_number(6);
+		assert_noop!(
+			Auction::buy_dutch(Origin::signed(CHARLIE), COLLECTION_ID_0, Some(TOKEN_ID_0), 1_499),
+			Error::<Test>::PriceTooHigh
+		);
+
+		let alice_balance = Balances::free_balance(ALICE);
+		let bob_balance = Balances::free_balance(BOB);
+		let charlie_balance = Balances::free_balance(CHARLIE);
+		assert_ok!(Auction::buy_dutch(
+			Origin::signed(CHARLIE),
+			COLLECTION_ID_0,
+			Some(TOKEN_ID_0),
+			1_500
+		));
+		System::assert_last_event(mock::Event::Auction(crate::Event::DutchAuctionSold(
+			BOB,
+			CHARLIE,
+			COLLECTION_ID_0,
+			TOKEN_ID_0,
+			1_500,
+		)));
+		// The author receives the royalty and the seller the rest of the price
+		assert_eq!(Balances::free_balance(ALICE), alice_balance + 150);
+		assert_eq!(Balances::free_balance(BOB), bob_balance + 1_350);
+		assert_eq!(Balances::free_balance(CHARLIE), charlie_balance - 1_500);
+		assert_eq!(NFTCore::root_owner(COLLECTION_ID_0, TOKEN_ID_0).unwrap(), CHARLIE);
+		assert_eq!(Auction::dutch_auctions(COLLECTION_ID_0, Some(TOKEN_ID_0)), None);
+		assert!(!pallet_nft_core::Locks::<Test>::contains_key(COLLECTION_ID_0, TOKEN_ID_0));
+	});
+}
+
+#[test]
+fn drop_mints_on_buy() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_mint();
+		let metadata: BoundedVec<u8, UniquesStringLimit> = stv("drop").try_into().unwrap();
+		assert_noop!(
+			Auction::create_drop(
+				Origin::signed(BOB),
+				COLLECTION_ID_0,
+				2,
+				metadata.clone(),
+				1_000,
+				1_000,
+				PriceDecay::Linear { duration: 10 }
+			),
+			Error::<Test>::NoPermission
+		);
+		assert_noop!(
+			Auction::create_drop(
+				Origin::signed(ALICE),
+				COLLECTION_ID_0,
+				0,
+				metadata.clone(),
+				1_000,
+				1_000,
+				PriceDecay::Linear { duration: 10 }
+			),
+			Error::<Test>::NoSupply
+		);
+		assert_ok!(Auction::create_drop(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			2,
+			metadata.clone(),
+			1_000,
+			1_000,
+			PriceDecay::Linear { duration: 10 }
+		));
+
+		let alice_balance = Balances::free_balance(ALICE);
+		let bob_balance = Balances::free_balance(BOB);
+		assert_ok!(Auction::buy_dutch(Origin::signed(BOB), COLLECTION_ID_0, None, 1_000));
+		System::assert_last_event(mock::Event::Auction(crate::Event::DutchAuctionSold(
+			ALICE,
+			BOB,
+			COLLECTION_ID_0,
+			1,
+			1_000,
+		)));
+		// The buyer pays the price to the issuer and the deposits of the minted NFT
+		let deposit = UniquesMetadataDepositBase::get() + DepositPerByte::get() * 4;
+		assert_eq!(
+			Balances::free_balance(BOB),
+			bob_balance - 1_000 - InstanceDeposit::get() - deposit
+		);
+		assert_eq!(Balances::free_balance(ALICE), alice_balance + 1_000);
+		let nft = NFTCore::nfts(COLLECTION_ID_0, 1).unwrap();
+		assert_eq!(nft.author, ALICE);
+		assert_eq!(nft.metadata, metadata);
+		assert_eq!(NFTCore::root_owner(COLLECTION_ID_0, 1).unwrap(), BOB);
+		assert_eq!(
+			Auction::dutch_auctions(COLLECTION_ID_0, None).unwrap().drop.unwrap().remaining,
+			1
+		);
+		// The drop ends once its supply is sold
+		assert_ok!(Auction::buy_dutch(Origin::signed(CHARLIE), COLLECTION_ID_0, None, 1_000));
+		assert_eq!(NFTCore::root_owner(COLLECTION_ID_0, 2).unwrap(), CHARLIE);
+		assert_eq!(Auction::dutch_auctions(COLLECTION_ID_0, None), None);
+	});
+}
+
+#[test]
+fn cancel_dutch_auction_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_mint();
+		assert_ok!(Auction::create_dutch_auction(
+			Origin::signed(BOB),
+			COLLECTION_ID_0,
+			TOKEN_ID_0,
+			1_000,
+			500,
+			PriceDecay::Linear { duration: 10 }
+		));
+		assert_noop!(
+			Auction::cancel_dutch_auction(Origin::signed(ALICE), COLLECTION_ID_0, Some(TOKEN_ID_0)),
+			Error::<Test>::NoPermission
+		);
+		assert_ok!(Auction::cancel_dutch_auction(
+			Origin::signed(BOB),
+			COLLECTION_ID_0,
+			Some(TOKEN_ID_0)
+		));
+		System::assert_last_event(mock::Event::Auction(crate::Event::DutchAuctionCancelled(
+			COLLECTION_ID_0,
+			Some(TOKEN_ID_0),
+		)));
+		assert_eq!(Auction::dutch_auctions(COLLECTION_ID_0, Some(TOKEN_ID_0)), None);
+		assert!(!pallet_nft_core::Locks::<Test>::contains_key(COLLECTION_ID_0, TOKEN_ID_0));
+	});
+}
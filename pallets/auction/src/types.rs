@@ -0,0 +1,62 @@
+use frame_support::pallet_prelude::*;
+
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+use scale_info::TypeInfo;
+
+/// An English auction of an NFT, settled at the end of its `end` block.
+#[derive(Encode, Decode, Eq, Copy, PartialEq, Clone, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct AuctionInfo<AccountId, Balance, BlockNumber> {
+	/// The root owner of the NFT when the auction was created, who receives the winning bid
+	pub seller: AccountId,
+	/// The lowest bid accepted
+	pub reserve: Balance,
+	/// The amount by which a bid must exceed the highest bid
+	pub min_increment: Balance,
+	/// The last block in which bids are accepted
+	pub end: BlockNumber,
+	/// The highest bidder and their bid, which is reserved until they are outbid
+	pub highest_bid: Option<(AccountId, Balance)>,
+}
+
+/// How the price of a Dutch auction decays from its start price to its floor price.
+#[derive(Encode, Decode, Eq, Copy, PartialEq, Clone, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum PriceDecay<Balance, BlockNumber> {
+	/// The price decays by the same amount every block, reaching the floor price `duration`
+	/// blocks after the start of the auction
+	Linear { duration: BlockNumber },
+	/// The price drops by `step` every `interval` blocks
+	Stepwise { step: Balance, interval: BlockNumber },
+}
+
+/// The NFTs minted to the buyers of a collection drop.
+#[derive(Encode, Decode, Eq, PartialEq, Clone, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct DropInfo<BoundedString> {
+	/// The number of NFTs left to be minted
+	pub remaining: u32,
+	/// The metadata of the minted NFTs
+	pub metadata: BoundedString,
+}
+
+/// A Dutch auction of an NFT or of the NFTs of a collection drop, whose first buyer at the
+/// current price wins.
+#[derive(Encode, Decode, Eq, PartialEq, Clone, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct DutchAuctionInfo<AccountId, Balance, BlockNumber, BoundedString> {
+	/// The root owner of the NFT or the issuer of the collection, who receives the price
+	pub seller: AccountId,
+	/// The price at the start of the auction
+	pub start_price: Balance,
+	/// The price below which the auction does not decay
+	pub floor_price: Balance,
+	/// The block at which the auction was created
+	pub start: BlockNumber,
+	/// How the price decays
+	pub decay: PriceDecay<Balance, BlockNumber>,
+	/// The NFTs left for sale, if the auction is a collection drop
+	pub drop: Option<DropInfo<BoundedString>>,
+}
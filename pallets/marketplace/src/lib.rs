@@ -0,0 +1,674 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::{
+	ensure,
+	traits::{Currency, ExistenceRequirement, ReservableCurrency},
+	transactional, BoundedVec,
+};
+use frame_system::ensure_signed;
+
+use pallet_nft_core::{
+	traits::{NftLock, RoyaltyHandler},
+	types::{AccountIdOrCollectionNftTuple, PropertySetter},
+	BalanceOf,
+};
+use sp_runtime::traits::Zero;
+
+use types::{ListingInfo, OfferInfo, SwapInfo, SwapWant};
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+pub mod types;
+
+pub type ListingInfoOf<T> = ListingInfo<
+	<T as frame_system::Config>::AccountId,
+	BalanceOf<T>,
+	<T as frame_system::Config>::BlockNumber,
+>;
+
+pub type OfferInfoOf<T> = OfferInfo<BalanceOf<T>, <T as frame_system::Config>::BlockNumber>;
+
+pub type SwapWantOf<T> = SwapWant<
+	<T as pallet_nft_core::Config>::CollectionId,
+	<T as pallet_nft_core::Config>::NftId,
+	BoundedVec<u8, <T as pallet_uniques::Config>::KeyLimit>,
+	BoundedVec<u8, <T as pallet_uniques::Config>::ValueLimit>,
+	BalanceOf<T>,
+>;
+pub type SwapInfoOf<T> = SwapInfo<<T as frame_system::Config>::AccountId, SwapWantOf<T>>;
+
+// Re-export pallet items so that they can be accessed from the crate namespace.
+pub use pallet::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+
+	use super::*;
+	use frame_support::{dispatch::DispatchResult, pallet_prelude::*};
+	use frame_system::pallet_prelude::*;
+
+	/// Configure the pallet by specifying the parameters and types on which it depends.
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_nft_core::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The maximum number of offers expiring in the same block.
+		#[pallet::constant]
+		type MaxOffersExpiringPerBlock: Get<u32>;
+	}
+
+	#[pallet::storage]
+	#[pallet::getter(fn listings)]
+	/// Stores the nfts listed for sale
+	pub type Listings<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::CollectionId,
+		Twox64Concat,
+		T::NftId,
+		ListingInfoOf<T>,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn offers)]
+	/// Stores the offers on nfts by bidder
+	pub type Offers<T: Config> = StorageNMap<
+		_,
+		(
+			NMapKey<Twox64Concat, T::CollectionId>,
+			NMapKey<Twox64Concat, T::NftId>,
+			NMapKey<Blake2_128Concat, T::AccountId>,
+		),
+		OfferInfoOf<T>,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn offer_expiries)]
+	/// Stores the offers expiring at each block, which are cleaned up when the block starts
+	pub type OfferExpiries<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		BoundedVec<(T::CollectionId, T::NftId, T::AccountId), T::MaxOffersExpiringPerBlock>,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn swaps)]
+	/// Stores the open swaps by offered nft
+	pub type Swaps<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::CollectionId, Twox64Concat, T::NftId, SwapInfoOf<T>>;
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		Listed(T::AccountId, T::CollectionId, T::NftId, BalanceOf<T>),
+		Unlisted(T::AccountId, T::CollectionId, T::NftId),
+		PriceUpdated(T::CollectionId, T::NftId, BalanceOf<T>),
+		Sold(T::AccountId, T::AccountId, T::CollectionId, T::NftId, BalanceOf<T>),
+		OfferPlaced(T::AccountId, T::CollectionId, T::NftId, BalanceOf<T>, T::BlockNumber),
+		OfferWithdrawn(T::AccountId, T::CollectionId, T::NftId),
+		OfferAccepted(T::AccountId, T::AccountId, T::CollectionId, T::NftId, BalanceOf<T>),
+		OfferExpired(T::AccountId, T::CollectionId, T::NftId),
+		SwapCreated(T::AccountId, T::CollectionId, T::NftId, SwapWantOf<T>),
+		SwapCancelled(T::CollectionId, T::NftId),
+		SwapClaimed(T::AccountId, T::AccountId, T::CollectionId, T::NftId),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		NoPermission,
+		AlreadyListed,
+		NotListed,
+		ListingExpired,
+		CannotBuyOwnNft,
+		PriceMismatch,
+		NonTransferable,
+		CannotOfferOnOwnNft,
+		NoOffer,
+		OfferExpired,
+		ExpiryInPast,
+		TooManyOffersExpiring,
+		SwapUnknown,
+		CannotClaimOwnSwap,
+		WrongNft,
+		ZeroOffer,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+			Self::expire_offers(now)
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Lists an NFT for sale at a fixed price
+		///
+		/// The NFT is locked in nft-core until it is sold or unlisted, so that it can neither
+		/// be sent nor burned in the meantime.
+		///
+		/// Parameters:
+		/// - `collection_id`: The collection of the NFT.
+		/// - `nft_id`: The NFT to be listed, the sender must be its root owner.
+		/// - `price`: The price of the NFT, including its royalty.
+		/// - `expires`: The block at which the listing expires, if any.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(
+			T::MaxNestingDepth::get() as u64 + 4,
+			2,
+		))]
+		#[transactional]
+		pub fn list(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			nft_id: T::NftId,
+			price: BalanceOf<T>,
+			expires: Option<T::BlockNumber>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(!Listings::<T>::contains_key(collection_id, nft_id), Error::<T>::AlreadyListed);
+			let nft = pallet_nft_core::Pallet::<T>::nfts(collection_id, nft_id)
+				.ok_or(pallet_nft_core::Error::<T>::NftUnknown)?;
+			ensure!(nft.transferable, Error::<T>::NonTransferable);
+			ensure!(
+				sender == pallet_nft_core::Pallet::<T>::root_owner(collection_id, nft_id)?,
+				Error::<T>::NoPermission
+			);
+			pallet_nft_core::Pallet::<T>::lock(collection_id, nft_id)?;
+			Listings::<T>::insert(
+				collection_id,
+				nft_id,
+				ListingInfo { seller: sender.clone(), price, expires },
+			);
+
+			Self::deposit_event(Event::Listed(sender, collection_id, nft_id, price));
+			Ok(())
+		}
+
+		/// Removes the listing of an NFT and unlocks it
+		///
+		/// Parameters:
+		/// - `collection_id`: The collection of the NFT.
+		/// - `nft_id`: The listed NFT, the sender must be its seller unless the listing has
+		///   expired.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 2))]
+		#[transactional]
+		pub fn unlist(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			nft_id: T::NftId,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let listing = Self::listings(collection_id, nft_id).ok_or(Error::<T>::NotListed)?;
+			ensure!(
+				sender == listing.seller || Self::is_expired(&listing),
+				Error::<T>::NoPermission
+			);
+			Listings::<T>::remove(collection_id, nft_id);
+			pallet_nft_core::Pallet::<T>::unlock(collection_id, nft_id);
+
+			Self::deposit_event(Event::Unlisted(listing.seller, collection_id, nft_id));
+			Ok(())
+		}
+
+		/// Changes the price of a listed NFT
+		///
+		/// Parameters:
+		/// - `collection_id`: The collection of the NFT.
+		/// - `nft_id`: The listed NFT, the sender must be its seller.
+		/// - `price`: The new price of the NFT, including its royalty.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		#[transactional]
+		pub fn update_price(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			nft_id: T::NftId,
+			price: BalanceOf<T>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			Listings::<T>::try_mutate(collection_id, nft_id, |listing| -> DispatchResult {
+				let listing = listing.as_mut().ok_or(Error::<T>::NotListed)?;
+				ensure!(sender == listing.seller, Error::<T>::NoPermission);
+				listing.price = price;
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::PriceUpdated(collection_id, nft_id, price));
+			Ok(())
+		}
+
+		/// Buys a listed NFT
+		///
+		/// The royalty of the NFT is paid to its author and royalty recipients, the rest of the
+		/// price to the seller. The NFT is then sent to the sender through nft-core.
+		///
+		/// Parameters:
+		/// - `collection_id`: The collection of the NFT.
+		/// - `nft_id`: The listed NFT.
+		/// - `price`: The price the sender expects to pay, which must match the listing so that
+		///   a price update cannot be slipped in before the purchase.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(
+			T::MaxNestingDepth::get() as u64 +
+				T::MaxRoyaltyRecipients::get() as u64 +
+				6 * T::MaxChildren::get() as u64 +
+				8,
+			T::MaxRoyaltyRecipients::get() as u64 + 3 * T::MaxChildren::get() as u64 + 8,
+		))]
+		#[transactional]
+		pub fn buy(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			nft_id: T::NftId,
+			price: BalanceOf<T>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let listing = Self::listings(collection_id, nft_id).ok_or(Error::<T>::NotListed)?;
+			ensure!(!Self::is_expired(&listing), Error::<T>::ListingExpired);
+			ensure!(sender != listing.seller, Error::<T>::CannotBuyOwnNft);
+			ensure!(price == listing.price, Error::<T>::PriceMismatch);
+			Listings::<T>::remove(collection_id, nft_id);
+			pallet_nft_core::Pallet::<T>::unlock(collection_id, nft_id);
+
+			let proceeds = pallet_nft_core::Pallet::<T>::pay_royalty(
+				&sender,
+				collection_id,
+				nft_id,
+				listing.price,
+			)?;
+			if !proceeds.is_zero() {
+				<T as pallet_nft_core::Config>::Currency::transfer(
+					&sender,
+					&listing.seller,
+					proceeds,
+					ExistenceRequirement::AllowDeath,
+				)?;
+			}
+			pallet_nft_core::Pallet::<T>::do_send(
+				collection_id,
+				nft_id,
+				AccountIdOrCollectionNftTuple::AccountId(sender.clone()),
+				None,
+			)?;
+
+			Self::deposit_event(Event::Sold(
+				listing.seller,
+				sender,
+				collection_id,
+				nft_id,
+				listing.price,
+			));
+			Ok(())
+		}
+
+		/// Places an offer on an NFT, whether it is listed or not
+		///
+		/// The amount is reserved from the sender until the offer is accepted, withdrawn or
+		/// expires. An earlier offer of the sender on the same NFT is replaced.
+		///
+		/// Parameters:
+		/// - `collection_id`: The collection of the NFT.
+		/// - `nft_id`: The NFT to bid on.
+		/// - `amount`: The amount offered, including the royalty of the NFT, more than zero.
+		/// - `expires`: The block at which the offer expires, after the current block.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(
+			T::MaxNestingDepth::get() as u64 + 5,
+			5,
+		))]
+		#[transactional]
+		pub fn place_offer(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			nft_id: T::NftId,
+			amount: BalanceOf<T>,
+			expires: T::BlockNumber,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(!amount.is_zero(), Error::<T>::ZeroOffer);
+			ensure!(expires > frame_system::Pallet::<T>::block_number(), Error::<T>::ExpiryInPast);
+			let nft = pallet_nft_core::Pallet::<T>::nfts(collection_id, nft_id)
+				.ok_or(pallet_nft_core::Error::<T>::NftUnknown)?;
+			ensure!(nft.transferable, Error::<T>::NonTransferable);
+			ensure!(
+				sender != pallet_nft_core::Pallet::<T>::root_owner(collection_id, nft_id)?,
+				Error::<T>::CannotOfferOnOwnNft
+			);
+
+			if let Some(offer) = Self::offers((collection_id, nft_id, &sender)) {
+				Self::remove_offer(collection_id, nft_id, &sender, &offer);
+			}
+			<T as pallet_nft_core::Config>::Currency::reserve(&sender, amount)?;
+			OfferExpiries::<T>::try_mutate(expires, |maybe_expiring| -> DispatchResult {
+				maybe_expiring
+					.get_or_insert_with(Default::default)
+					.try_push((collection_id, nft_id, sender.clone()))
+					.map_err(|_| Error::<T>::TooManyOffersExpiring.into())
+			})?;
+			Offers::<T>::insert((collection_id, nft_id, &sender), OfferInfo { amount, expires });
+
+			Self::deposit_event(Event::OfferPlaced(sender, collection_id, nft_id, amount, expires));
+			Ok(())
+		}
+
+		/// Withdraws an offer and unreserves its amount
+		///
+		/// Parameters:
+		/// - `collection_id`: The collection of the NFT.
+		/// - `nft_id`: The NFT the sender has bid on.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 3))]
+		#[transactional]
+		pub fn withdraw_offer(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			nft_id: T::NftId,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let offer =
+				Self::offers((collection_id, nft_id, &sender)).ok_or(Error::<T>::NoOffer)?;
+			Self::remove_offer(collection_id, nft_id, &sender, &offer);
+
+			Self::deposit_event(Event::OfferWithdrawn(sender, collection_id, nft_id));
+			Ok(())
+		}
+
+		/// Accepts an offer on an NFT
+		///
+		/// The royalty of the NFT is paid to its author and royalty recipients, the rest of the
+		/// amount to the sender, and the NFT is sent to the bidder through nft-core. A listing of
+		/// the NFT is removed, other offers on it stay valid until they expire.
+		///
+		/// Parameters:
+		/// - `collection_id`: The collection of the NFT.
+		/// - `nft_id`: The NFT, the sender must be its root owner.
+		/// - `bidder`: The account whose offer is accepted.
+		/// - `amount`: The amount the sender expects to receive, which must match the offer so
+		///   that the bidder cannot lower it before it is accepted.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(
+			T::MaxNestingDepth::get() as u64 +
+				T::MaxRoyaltyRecipients::get() as u64 +
+				6 * T::MaxChildren::get() as u64 +
+				10,
+			T::MaxRoyaltyRecipients::get() as u64 + 3 * T::MaxChildren::get() as u64 + 12,
+		))]
+		#[transactional]
+		pub fn accept_offer(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			nft_id: T::NftId,
+			bidder: T::AccountId,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let offer =
+				Self::offers((collection_id, nft_id, &bidder)).ok_or(Error::<T>::NoOffer)?;
+			ensure!(
+				frame_system::Pallet::<T>::block_number() < offer.expires,
+				Error::<T>::OfferExpired
+			);
+			ensure!(amount == offer.amount, Error::<T>::PriceMismatch);
+			ensure!(
+				sender == pallet_nft_core::Pallet::<T>::root_owner(collection_id, nft_id)?,
+				Error::<T>::NoPermission
+			);
+			if let Some(listing) = Listings::<T>::take(collection_id, nft_id) {
+				pallet_nft_core::Pallet::<T>::unlock(collection_id, nft_id);
+				Self::deposit_event(Event::Unlisted(listing.seller, collection_id, nft_id));
+			}
+			Self::remove_offer(collection_id, nft_id, &bidder, &offer);
+
+			let proceeds = pallet_nft_core::Pallet::<T>::pay_royalty(
+				&bidder,
+				collection_id,
+				nft_id,
+				offer.amount,
+			)?;
+			if !proceeds.is_zero() {
+				<T as pallet_nft_core::Config>::Currency::transfer(
+					&bidder,
+					&sender,
+					proceeds,
+					ExistenceRequirement::AllowDeath,
+				)?;
+			}
+			pallet_nft_core::Pallet::<T>::do_send(
+				collection_id,
+				nft_id,
+				AccountIdOrCollectionNftTuple::AccountId(bidder.clone()),
+				None,
+			)?;
+
+			Self::deposit_event(Event::OfferAccepted(
+				sender,
+				bidder,
+				collection_id,
+				nft_id,
+				offer.amount,
+			));
+			Ok(())
+		}
+
+		/// Offers an NFT in exchange for a specific NFT, any NFT of a collection or an amount of
+		/// balance
+		///
+		/// The NFT is locked in nft-core until the swap is claimed or cancelled.
+		///
+		/// Parameters:
+		/// - `collection_id`: The collection of the offered NFT.
+		/// - `nft_id`: The offered NFT, the sender must be its root owner.
+		/// - `want`: What the sender wants in exchange.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(
+			T::MaxNestingDepth::get() as u64 + 3,
+			2,
+		))]
+		#[transactional]
+		pub fn create_swap(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			nft_id: T::NftId,
+			want: SwapWantOf<T>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let nft = pallet_nft_core::Pallet::<T>::nfts(collection_id, nft_id)
+				.ok_or(pallet_nft_core::Error::<T>::NftUnknown)?;
+			ensure!(nft.transferable, Error::<T>::NonTransferable);
+			ensure!(
+				sender == pallet_nft_core::Pallet::<T>::root_owner(collection_id, nft_id)?,
+				Error::<T>::NoPermission
+			);
+			pallet_nft_core::Pallet::<T>::lock(collection_id, nft_id)?;
+			Swaps::<T>::insert(
+				collection_id,
+				nft_id,
+				SwapInfo { creator: sender.clone(), want: want.clone() },
+			);
+
+			Self::deposit_event(Event::SwapCreated(sender, collection_id, nft_id, want));
+			Ok(())
+		}
+
+		/// Cancels a swap and unlocks the offered NFT
+		///
+		/// Parameters:
+		/// - `collection_id`: The collection of the offered NFT.
+		/// - `nft_id`: The offered NFT, the sender must be the creator of the swap.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 2))]
+		#[transactional]
+		pub fn cancel_swap(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			nft_id: T::NftId,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let swap = Self::swaps(collection_id, nft_id).ok_or(Error::<T>::SwapUnknown)?;
+			ensure!(sender == swap.creator, Error::<T>::NoPermission);
+			Swaps::<T>::remove(collection_id, nft_id);
+			pallet_nft_core::Pallet::<T>::unlock(collection_id, nft_id);
+
+			Self::deposit_event(Event::SwapCancelled(collection_id, nft_id));
+			Ok(())
+		}
+
+		/// Claims a swap, exchanging what its creator wants for the offered NFT
+		///
+		/// Both NFTs are transferred through nft-core, and so through `pallet_uniques`, keeping
+		/// NFTs nested under them attached. When the creator wants balance, the royalty of the
+		/// offered NFT is paid from it. Either both legs of the swap succeed or neither does.
+		///
+		/// A property wanted from the given NFT must have been set by the issuer of its
+		/// collection or through the protocol origin, as the root owner of an NFT may set its
+		/// properties too.
+		///
+		/// Parameters:
+		/// - `collection_id`: The collection of the offered NFT.
+		/// - `nft_id`: The offered NFT.
+		/// - `with`: The NFT given in exchange, the sender must be its root owner. Must be `None`
+		///   when the creator wants balance.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(
+			2 * T::MaxNestingDepth::get() as u64 +
+				T::MaxRoyaltyRecipients::get() as u64 +
+				12 * T::MaxChildren::get() as u64 +
+				10,
+			T::MaxRoyaltyRecipients::get() as u64 + 6 * T::MaxChildren::get() as u64 + 12,
+		))]
+		#[transactional]
+		pub fn claim_swap(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			nft_id: T::NftId,
+			with: Option<(T::CollectionId, T::NftId)>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let swap = Swaps::<T>::take(collection_id, nft_id).ok_or(Error::<T>::SwapUnknown)?;
+			ensure!(sender != swap.creator, Error::<T>::CannotClaimOwnSwap);
+			pallet_nft_core::Pallet::<T>::unlock(collection_id, nft_id);
+
+			match (swap.want, with) {
+				(SwapWant::Balance(amount), None) => {
+					let proceeds = pallet_nft_core::Pallet::<T>::pay_royalty(
+						&sender,
+						collection_id,
+						nft_id,
+						amount,
+					)?;
+					if !proceeds.is_zero() {
+						<T as pallet_nft_core::Config>::Currency::transfer(
+							&sender,
+							&swap.creator,
+							proceeds,
+							ExistenceRequirement::AllowDeath,
+						)?;
+					}
+				},
+				(SwapWant::Nft(want_collection_id, want_nft_id), Some(with)) => {
+					ensure!(with == (want_collection_id, want_nft_id), Error::<T>::WrongNft);
+					Self::send_swapped(with, &sender, &swap.creator)?;
+				},
+				(SwapWant::Collection(want_collection_id, filter), Some(with)) => {
+					ensure!(with.0 == want_collection_id, Error::<T>::WrongNft);
+					if let Some((key, value)) = filter {
+						// Root owners may set properties of their own NFTs, so a property they
+						// wrote is not trusted
+						let property =
+							pallet_nft_core::Pallet::<T>::properties((with.0, Some(with.1), key));
+						ensure!(
+							property.map_or(false, |(property, setter, _)| {
+								property == value && setter != PropertySetter::Owner
+							}),
+							Error::<T>::WrongNft
+						);
+					}
+					Self::send_swapped(with, &sender, &swap.creator)?;
+				},
+				_ => return Err(Error::<T>::WrongNft.into()),
+			}
+			pallet_nft_core::Pallet::<T>::do_send(
+				collection_id,
+				nft_id,
+				AccountIdOrCollectionNftTuple::AccountId(sender.clone()),
+				Some(swap.creator.clone()),
+			)?;
+
+			Self::deposit_event(Event::SwapClaimed(swap.creator, sender, collection_id, nft_id));
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Sends the NFT given in exchange for a swap from its root owner to the creator of the
+		/// swap.
+		fn send_swapped(
+			(collection_id, nft_id): (T::CollectionId, T::NftId),
+			owner: &T::AccountId,
+			creator: &T::AccountId,
+		) -> DispatchResult {
+			pallet_nft_core::Pallet::<T>::do_send(
+				collection_id,
+				nft_id,
+				AccountIdOrCollectionNftTuple::AccountId(creator.clone()),
+				Some(owner.clone()),
+			)?;
+			Ok(())
+		}
+
+		/// Unreserves and removes the offers expiring at block `now`.
+		fn expire_offers(now: T::BlockNumber) -> Weight {
+			let expiring = OfferExpiries::<T>::take(now).unwrap_or_default();
+			for (collection_id, nft_id, bidder) in expiring.iter() {
+				if let Some(offer) = Offers::<T>::take((collection_id, nft_id, bidder)) {
+					<T as pallet_nft_core::Config>::Currency::unreserve(bidder, offer.amount);
+					Self::deposit_event(Event::OfferExpired(
+						bidder.clone(),
+						*collection_id,
+						*nft_id,
+					));
+				}
+			}
+			T::DbWeight::get()
+				.reads_writes(1 + expiring.len() as u64, 1 + 2 * expiring.len() as u64)
+		}
+
+		/// Removes an offer and unreserves its amount.
+		fn remove_offer(
+			collection_id: T::CollectionId,
+			nft_id: T::NftId,
+			bidder: &T::AccountId,
+			offer: &OfferInfoOf<T>,
+		) {
+			Offers::<T>::remove((collection_id, nft_id, bidder));
+			OfferExpiries::<T>::mutate_exists(offer.expires, |maybe_expiring| {
+				if let Some(expiring) = maybe_expiring {
+					expiring.retain(|entry| entry != &(collection_id, nft_id, bidder.clone()));
+					if expiring.is_empty() {
+						*maybe_expiring = None;
+					}
+				}
+			});
+			<T as pallet_nft_core::Config>::Currency::unreserve(bidder, offer.amount);
+		}
+
+		fn is_expired(listing: &ListingInfoOf<T>) -> bool {
+			listing
+				.expires
+				.map_or(false, |expires| frame_system::Pallet::<T>::block_number() >= expires)
+		}
+	}
+}
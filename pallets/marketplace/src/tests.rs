@@ -0,0 +1,751 @@
+use frame_support::{assert_noop, assert_ok, traits::Hooks};
+
+use super::*;
+use mock::*;
+use pallet_nft_core::types::AccountIdOrCollectionNftTuple::{
+	AccountId as ToAccount, CollectionAndNftTuple as ToNft,
+};
+use sp_runtime::Permill;
+
+type NFTCore = pallet_nft_core::Pallet<Test>;
+
+/// Turns a string into a Vec
+fn stv(s: &str) -> Vec<u8> {
+	s.as_bytes().to_vec()
+}
+
+/// Creates a collection owned by ALICE and mints an NFT authored by ALICE with a 10% royalty
+/// to BOB
+fn basic_mint() {
+	assert_ok!(NFTCore::create_collection(
+		Origin::signed(ALICE),
+		stv("metadata"),
+		None,
+		stv("SYM"),
+		None
+	));
+	assert_ok!(NFTCore::mint_nft(
+		Origin::signed(ALICE),
+		COLLECTION_ID_0,
+		Some(BOB),
+		Some(ALICE),
+		Some(Permill::from_percent(10)),
+		None,
+		Some(stv("metadata")),
+		true
+	));
+}
+
+#[test]
+fn list_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_mint();
+		// Only the root owner can list the NFT
+		assert_noop!(
+			Marketplace::list(Origin::signed(ALICE), COLLECTION_ID_0, TOKEN_ID_0, 1_000, None),
+			Error::<Test>::NoPermission
+		);
+		assert_ok!(Marketplace::list(
+			Origin::signed(BOB),
+			COLLECTION_ID_0,
+			TOKEN_ID_0,
+			1_000,
+			None
+		));
+		System::assert_last_event(mock::Event::Marketplace(crate::Event::Listed(
+			BOB,
+			COLLECTION_ID_0,
+			TOKEN_ID_0,
+			1_000,
+		)));
+		assert_eq!(
+			Marketplace::listings(COLLECTION_ID_0, TOKEN_ID_0),
+			Some(ListingInfo { seller: BOB, price: 1_000, expires: None })
+		);
+		assert_noop!(
+			Marketplace::list(Origin::signed(BOB), COLLECTION_ID_0, TOKEN_ID_0, 1_000, None),
+			Error::<Test>::AlreadyListed
+		);
+		// The listed NFT is locked in nft-core
+		assert_noop!(
+			NFTCore::send(Origin::signed(BOB), COLLECTION_ID_0, TOKEN_ID_0, ToAccount(CHARLIE)),
+			pallet_nft_core::Error::<Test>::NftLocked
+		);
+		assert_noop!(
+			NFTCore::burn_nft(Origin::signed(BOB), COLLECTION_ID_0, TOKEN_ID_0),
+			pallet_nft_core::Error::<Test>::NftLocked
+		);
+	});
+}
+
+#[test]
+fn unlist_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_mint();
+		assert_noop!(
+			Marketplace::unlist(Origin::signed(BOB), COLLECTION_ID_0, TOKEN_ID_0),
+			Error::<Test>::NotListed
+		);
+		assert_ok!(Marketplace::list(
+			Origin::signed(BOB),
+			COLLECTION_ID_0,
+			TOKEN_ID_0,
+			1_000,
+			Some(10)
+		));
+		// Only the seller can unlist a listing before it expires
+		assert_noop!(
+			Marketplace::unlist(Origin::signed(CHARLIE), COLLECTION_ID_0, TOKEN_ID_0),
+			Error::<Test>::NoPermission
+		);
+		assert_ok!(Marketplace::unlist(Origin::signed(BOB), COLLECTION_ID_0, TOKEN_ID_0));
+		System::assert_last_event(mock::Event::Marketplace(crate::Event::Unlisted(
+			BOB,
+			COLLECTION_ID_0,
+			TOKEN_ID_0,
+		)));
+		assert_eq!(Marketplace::listings(COLLECTION_ID_0, TOKEN_ID_0), None);
+		// The NFT can be sent again
+		assert_ok!(NFTCore::send(
+			Origin::signed(BOB),
+			COLLECTION_ID_0,
+			TOKEN_ID_0,
+			ToAccount(CHARLIE)
+		));
+		// Anyone can clean up an expired listing
+		assert_ok!(Marketplace::list(
+			Origin::signed(CHARLIE),
+			COLLECTION_ID_0,
+			TOKEN_ID_0,
+			1_000,
+			Some(10)
+		));
+		System::set_block_number(10);
+		assert_ok!(Marketplace::unlist(Origin::signed(BOB), COLLECTION_ID_0, TOKEN_ID_0));
+		assert_eq!(Marketplace::listings(COLLECTION_ID_0, TOKEN_ID_0), None);
+	});
+}
+
+#[test]
+fn update_price_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_mint();
+		assert_ok!(Marketplace::list(
+			Origin::signed(BOB),
+			COLLECTION_ID_0,
+			TOKEN_ID_0,
+			1_000,
+			None
+		));
+		assert_noop!(
+			Marketplace::update_price(Origin::signed(CHARLIE), COLLECTION_ID_0, TOKEN_ID_0, 500),
+			Error::<Test>::NoPermission
+		);
+		assert_ok!(Marketplace::update_price(
+			Origin::signed(BOB),
+			COLLECTION_ID_0,
+			TOKEN_ID_0,
+			500
+		));
+		System::assert_last_event(mock::Event::Marketplace(crate::Event::PriceUpdated(
+			COLLECTION_ID_0,
+			TOKEN_ID_0,
+			500,
+		)));
+		assert_eq!(Marketplace::listings(COLLECTION_ID_0, TOKEN_ID_0).unwrap().price, 500);
+		// A buyer expecting the old price is refused
+		assert_noop!(
+			Marketplace::buy(Origin::signed(CHARLIE), COLLECTION_ID_0, TOKEN_ID_0, 1_000),
+			Error::<Test>::PriceMismatch
+		);
+	});
+}
+
+#[test]
+fn buy_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_mint();
+		assert_noop!(
+			Marketplace::buy(Origin::signed(CHARLIE), COLLECTION_ID_0, TOKEN_ID_0, 1_000),
+			Error::<Test>::NotListed
+		);
+		assert_ok!(Marketplace::list(
+			Origin::signed(BOB),
+			COLLECTION_ID_0,
+			TOKEN_ID_0,
+			1_000,
+			Some(10)
+		));
+		assert_noop!(
+			Marketplace::buy(Origin::signed(BOB), COLLECTION_ID_0, TOKEN_ID_0, 1_000),
+			Error::<Test>::CannotBuyOwnNft
+		);
+
+		let alice_balance = Balances::free_balance(ALICE);
+		let bob_balance = Balances::free_balance(BOB);
+		let charlie_balance = Balances::free_balance(CHARLIE);
+		assert_ok!(Marketplace::buy(Origin::signed(CHARLIE), COLLECTION_ID_0, TOKEN_ID_0, 1_000));
+		System::assert_last_event(mock::Event::Marketplace(crate::Event::Sold(
+			BOB,
+			CHARLIE,
+			COLLECTION_ID_0,
+			TOKEN_ID_0,
+			1_000,
+		)));
+		// The author receives the royalty and the seller the rest of the price
+		assert_eq!(Balances::free_balance(ALICE), alice_balance + 100);
+		assert_eq!(Balances::free_balance(BOB), bob_balance + 900);
+		assert_eq!(Balances::free_balance(CHARLIE), charlie_balance - 1_000);
+		assert_eq!(NFTCore::root_owner(COLLECTION_ID_0, TOKEN_ID_0).unwrap(), CHARLIE);
+		assert_eq!(Marketplace::listings(COLLECTION_ID_0, TOKEN_ID_0), None);
+		assert!(!pallet_nft_core::Locks::<Test>::contains_key(COLLECTION_ID_0, TOKEN_ID_0));
+	});
+}
+
+#[test]
+fn nested_nfts_of_listing_are_locked() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_mint();
+		assert_ok!(NFTCore::mint_nft(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			Some(BOB),
+			Some(ALICE),
+			Some(Permill::zero()),
+			None,
+			Some(stv("metadata")),
+			true
+		));
+		assert_ok!(NFTCore::send(
+			Origin::signed(BOB),
+			COLLECTION_ID_0,
+			1,
+			ToNft(COLLECTION_ID_0, 0)
+		));
+		assert_ok!(Marketplace::list(
+			Origin::signed(BOB),
+			COLLECTION_ID_0,
+			TOKEN_ID_0,
+			1_000,
+			None
+		));
+		// The seller can neither take the nested NFT out of the listed one nor sell it apart
+		assert_noop!(
+			NFTCore::send(Origin::signed(BOB), COLLECTION_ID_0, 1, ToAccount(BOB)),
+			pallet_nft_core::Error::<Test>::NftLocked
+		);
+		assert_noop!(
+			NFTCore::burn_nft(Origin::signed(BOB), COLLECTION_ID_0, 1),
+			pallet_nft_core::Error::<Test>::NftLocked
+		);
+		assert_noop!(
+			Marketplace::list(Origin::signed(BOB), COLLECTION_ID_0, 1, 1_000, None),
+			pallet_nft_core::Error::<Test>::NftLocked
+		);
+		// The buyer receives the nested NFT along with the listed one
+		assert_ok!(Marketplace::buy(Origin::signed(CHARLIE), COLLECTION_ID_0, TOKEN_ID_0, 1_000));
+		assert_eq!(NFTCore::root_owner(COLLECTION_ID_0, 1).unwrap(), CHARLIE);
+	});
+}
+
+#[test]
+fn buy_expired_listing_fails() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_mint();
+		assert_ok!(Marketplace::list(
+			Origin::signed(BOB),
+			COLLECTION_ID_0,
+			TOKEN_ID_0,
+			1_000,
+			Some(10)
+		));
+		System::set_block_number(10);
+		assert_noop!(
+			Marketplace::buy(Origin::signed(CHARLIE), COLLECTION_ID_0, TOKEN_ID_0, 1_000),
+			Error::<Test>::ListingExpired
+		);
+	});
+}
+
+#[test]
+fn non_transferable_nft_cannot_be_listed() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_mint();
+		assert_ok!(NFTCore::mint_nft(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			None,
+			Some(ALICE),
+			Some(Permill::zero()),
+			None,
+			Some(stv("metadata")),
+			false
+		));
+		assert_noop!(
+			Marketplace::list(Origin::signed(ALICE), COLLECTION_ID_0, 1, 1_000, None),
+			Error::<Test>::NonTransferable
+		);
+	});
+}
+
+#[test]
+fn place_offer_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_mint();
+		assert_noop!(
+			Marketplace::place_offer(Origin::signed(CHARLIE), COLLECTION_ID_0, TOKEN_ID_0, 0, 10),
+			Error::<Test>::ZeroOffer
+		);
+		assert_noop!(
+			Marketplace::place_offer(Origin::signed(CHARLIE), COLLECTION_ID_0, TOKEN_ID_0, 500, 1),
+			Error::<Test>::ExpiryInPast
+		);
+		assert_noop!(
+			Marketplace::place_offer(Origin::signed(BOB), COLLECTION_ID_0, TOKEN_ID_0, 500, 10),
+			Error::<Test>::CannotOfferOnOwnNft
+		);
+		assert_ok!(Marketplace::place_offer(
+			Origin::signed(CHARLIE),
+			COLLECTION_ID_0,
+			TOKEN_ID_0,
+			500,
+			10
+		));
+		System::assert_last_event(mock::Event::Marketplace(crate::Event::OfferPlaced(
+			CHARLIE,
+			COLLECTION_ID_0,
+			TOKEN_ID_0,
+			500,
+			10,
+		)));
+		assert_eq!(Balances::reserved_balance(&CHARLIE), 500);
+		assert_eq!(
+			Marketplace::offers((COLLECTION_ID_0, TOKEN_ID_0, CHARLIE)),
+			Some(OfferInfo { amount: 500, expires: 10 })
+		);
+		// A new offer replaces the earlier one
+		assert_ok!(Marketplace::place_offer(
+			Origin::signed(CHARLIE),
+			COLLECTION_ID_0,
+			TOKEN_ID_0,
+			800,
+			20
+		));
+		assert_eq!(Balances::reserved_balance(&CHARLIE), 800);
+		assert_eq!(Marketplace::offer_expiries(10), None);
+		assert_eq!(Marketplace::offer_expiries(20).unwrap().len(), 1);
+		// Only `MaxOffersExpiringPerBlock` offers may expire in the same block
+		assert_ok!(Marketplace::place_offer(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			TOKEN_ID_0,
+			500,
+			20
+		));
+		assert_ok!(NFTCore::mint_nft(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			Some(BOB),
+			Some(ALICE),
+			Some(Permill::zero()),
+			None,
+			Some(stv("metadata")),
+			true
+		));
+		assert_noop!(
+			Marketplace::place_offer(Origin::signed(ALICE), COLLECTION_ID_0, 1, 500, 20),
+			Error::<Test>::TooManyOffersExpiring
+		);
+	});
+}
+
+#[test]
+fn withdraw_offer_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_mint();
+		assert_noop!(
+			Marketplace::withdraw_offer(Origin::signed(CHARLIE), COLLECTION_ID_0, TOKEN_ID_0),
+			Error::<Test>::NoOffer
+		);
+		assert_ok!(Marketplace::place_offer(
+			Origin::signed(CHARLIE),
+			COLLECTION_ID_0,
+			TOKEN_ID_0,
+			500,
+			10
+		));
+		assert_ok!(Marketplace::withdraw_offer(
+			Origin::signed(CHARLIE),
+			COLLECTION_ID_0,
+			TOKEN_ID_0
+		));
+		System::assert_last_event(mock::Event::Marketplace(crate::Event::OfferWithdrawn(
+			CHARLIE,
+			COLLECTION_ID_0,
+			TOKEN_ID_0,
+		)));
+		assert_eq!(Balances::reserved_balance(&CHARLIE), 0);
+		assert_eq!(Marketplace::offers((COLLECTION_ID_0, TOKEN_ID_0, CHARLIE)), None);
+		assert_eq!(Marketplace::offer_expiries(10), None);
+	});
+}
+
+#[test]
+fn accept_offer_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_mint();
+		assert_ok!(Marketplace::list(
+			Origin::signed(BOB),
+			COLLECTION_ID_0,
+			TOKEN_ID_0,
+			1_000,
+			None
+		));
+		assert_ok!(Marketplace::place_offer(
+			Origin::signed(CHARLIE),
+			COLLECTION_ID_0,
+			TOKEN_ID_0,
+			800,
+			10
+		));
+		assert_ok!(Marketplace::place_offer(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			TOKEN_ID_0,
+			500,
+			10
+		));
+		assert_noop!(
+			Marketplace::accept_offer(
+				Origin::signed(ALICE),
+				COLLECTION_ID_0,
+				TOKEN_ID_0,
+				CHARLIE,
+				800
+			),
+			Error::<Test>::NoPermission
+		);
+		assert_noop!(
+			Marketplace::accept_offer(
+				Origin::signed(BOB),
+				COLLECTION_ID_0,
+				TOKEN_ID_0,
+				CHARLIE,
+				900
+			),
+			Error::<Test>::PriceMismatch
+		);
+
+		let alice_balance = Balances::free_balance(ALICE);
+		let bob_balance = Balances::free_balance(BOB);
+		let charlie_balance = Balances::free_balance(CHARLIE);
+		assert_ok!(Marketplace::accept_offer(
+			Origin::signed(BOB),
+			COLLECTION_ID_0,
+			TOKEN_ID_0,
+			CHARLIE,
+			800
+		));
+		System::assert_last_event(mock::Event::Marketplace(crate::Event::OfferAccepted(
+			BOB,
+			CHARLIE,
+			COLLECTION_ID_0,
+			TOKEN_ID_0,
+			800,
+		)));
+		// The author receives the royalty and the owner the rest of the offer
+		assert_eq!(Balances::free_balance(ALICE), alice_balance + 80);
+		assert_eq!(Balances::free_balance(BOB), bob_balance + 720);
+		assert_eq!(Balances::free_balance(CHARLIE), charlie_balance);
+		assert_eq!(Balances::reserved_balance(&CHARLIE), 0);
+		assert_eq!(NFTCore::root_owner(COLLECTION_ID_0, TOKEN_ID_0).unwrap(), CHARLIE);
+		// The listing is removed, other offers stay valid
+		assert_eq!(Marketplace::listings(COLLECTION_ID_0, TOKEN_ID_0), None);
+		assert_eq!(Marketplace::offers((COLLECTION_ID_0, TOKEN_ID_0, CHARLIE)), None);
+		assert!(Marketplace::offers((COLLECTION_ID_0, TOKEN_ID_0, ALICE)).is_some());
+		assert_ok!(Marketplace::accept_offer(
+			Origin::signed(CHARLIE),
+			COLLECTION_ID_0,
+			TOKEN_ID_0,
+			ALICE,
+			500
+		));
+		assert_eq!(NFTCore::root_owner(COLLECTION_ID_0, TOKEN_ID_0).unwrap(), ALICE);
+	});
+}
+
+#[test]
+fn offers_expire_on_initialize() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_mint();
+		assert_ok!(Marketplace::place_offer(
+			Origin::signed(CHARLIE),
+			COLLECTION_ID_0,
+			TOKEN_ID_0,
+			500,
+			10
+		));
+		System::set_block_number(10);
+		assert_noop!(
+			Marketplace::accept_offer(
+				Origin::signed(BOB),
+				COLLECTION_ID_0,
+				TOKEN_ID_0,
+				CHARLIE,
+				500
+			),
+			Error::<Test>::OfferExpired
+		);
+		Marketplace::on_initialize(10);
+		System::assert_last_event(mock::Event::Marketplace(crate::Event::OfferExpired(
+			CHARLIE,
+			COLLECTION_ID_0,
+			TOKEN_ID_0,
+		)));
+		assert_eq!(Balances::reserved_balance(&CHARLIE), 0);
+		assert_eq!(Marketplace::offers((COLLECTION_ID_0, TOKEN_ID_0, CHARLIE)), None);
+		assert_eq!(Marketplace::offer_expiries(10), None);
+	});
+}
+
+/// Mints an NFT to CHARLIE into the collection of ALICE
+fn mint_to_charlie() {
+	assert_ok!(NFTCore::mint_nft(
+		Origin::signed(ALICE),
+		COLLECTION_ID_0,
+		Some(CHARLIE),
+		Some(ALICE),
+		Some(Permill::zero()),
+		None,
+		Some(stv("metadata")),
+		true
+	));
+}
+
+#[test]
+fn create_swap_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_mint();
+		assert_noop!(
+			Marketplace::create_swap(
+				Origin::signed(ALICE),
+				COLLECTION_ID_0,
+				TOKEN_ID_0,
+				SwapWant::Balance(1_000)
+			),
+			Error::<Test>::NoPermission
+		);
+		assert_ok!(Marketplace::create_swap(
+			Origin::signed(BOB),
+			COLLECTION_ID_0,
+			TOKEN_ID_0,
+			SwapWant::Nft(COLLECTION_ID_0, 1)
+		));
+		System::assert_last_event(mock::Event::Marketplace(crate::Event::SwapCreated(
+			BOB,
+			COLLECTION_ID_0,
+			TOKEN_ID_0,
+			SwapWant::Nft(COLLECTION_ID_0, 1),
+		)));
+		assert_eq!(
+			Marketplace::swaps(COLLECTION_ID_0, TOKEN_ID_0),
+			Some(SwapInfo { creator: BOB, want: SwapWant::Nft(COLLECTION_ID_0, 1) })
+		);
+		// The offered NFT is locked in nft-core
+		assert_noop!(
+			NFTCore::send(Origin::signed(BOB), COLLECTION_ID_0, TOKEN_ID_0, ToAccount(CHARLIE)),
+			pallet_nft_core::Error::<Test>::NftLocked
+		);
+		assert_noop!(
+			Marketplace::cancel_swap(Origin::signed(CHARLIE), COLLECTION_ID_0, TOKEN_ID_0),
+			Error::<Test>::NoPermission
+		);
+		assert_ok!(Marketplace::cancel_swap(Origin::signed(BOB), COLLECTION_ID_0, TOKEN_ID_0));
+		System::assert_last_event(mock::Event::Marketplace(crate::Event::SwapCancelled(
+			COLLECTION_ID_0,
+			TOKEN_ID_0,
+		)));
+		assert_eq!(Marketplace::swaps(COLLECTION_ID_0, TOKEN_ID_0), None);
+		assert!(!pallet_nft_core::Locks::<Test>::contains_key(COLLECTION_ID_0, TOKEN_ID_0));
+	});
+}
+
+#[test]
+fn swap_nft_for_nft_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_mint();
+		mint_to_charlie();
+		mint_to_charlie();
+		assert_ok!(Marketplace::create_swap(
+			Origin::signed(BOB),
+			COLLECTION_ID_0,
+			TOKEN_ID_0,
+			SwapWant::Nft(COLLECTION_ID_0, 1)
+		));
+		assert_noop!(
+			Marketplace::claim_swap(Origin::signed(BOB), COLLECTION_ID_0, TOKEN_ID_0, None),
+			Error::<Test>::CannotClaimOwnSwap
+		);
+		assert_noop!(
+			Marketplace::claim_swap(Origin::signed(CHARLIE), COLLECTION_ID_0, TOKEN_ID_0, None),
+			Error::<Test>::WrongNft
+		);
+		assert_noop!(
+			Marketplace::claim_swap(
+				Origin::signed(CHARLIE),
+				COLLECTION_ID_0,
+				TOKEN_ID_0,
+				Some((COLLECTION_ID_0, 2))
+			),
+			Error::<Test>::WrongNft
+		);
+		// Only the owner of the wanted NFT can give it
+		assert_noop!(
+			Marketplace::claim_swap(
+				Origin::signed(ALICE),
+				COLLECTION_ID_0,
+				TOKEN_ID_0,
+				Some((COLLECTION_ID_0, 1))
+			),
+			pallet_nft_core::Error::<Test>::NoPermission
+		);
+		assert_ok!(Marketplace::claim_swap(
+			Origin::signed(CHARLIE),
+			COLLECTION_ID_0,
+			TOKEN_ID_0,
+			Some((COLLECTION_ID_0, 1))
+		));
+		System::assert_last_event(mock::Event::Marketplace(crate::Event::SwapClaimed(
+			BOB,
+			CHARLIE,
+			COLLECTION_ID_0,
+			TOKEN_ID_0,
+		)));
+		assert_eq!(NFTCore::root_owner(COLLECTION_ID_0, TOKEN_ID_0).unwrap(), CHARLIE);
+		assert_eq!(NFTCore::root_owner(COLLECTION_ID_0, 1).unwrap(), BOB);
+		assert_eq!(Marketplace::swaps(COLLECTION_ID_0, TOKEN_ID_0), None);
+		assert!(!pallet_nft_core::Locks::<Test>::contains_key(COLLECTION_ID_0, TOKEN_ID_0));
+	});
+}
+
+#[test]
+fn swap_for_any_nft_of_collection_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_mint();
+		mint_to_charlie();
+		mint_to_charlie();
+		let key: BoundedVec<u8, KeyLimit> = stv("rarity").try_into().unwrap();
+		let value: BoundedVec<u8, ValueLimit> = stv("rare").try_into().unwrap();
+		assert_ok!(NFTCore::set_property(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			Some(2),
+			key.clone(),
+			value.clone()
+		));
+		assert_ok!(Marketplace::create_swap(
+			Origin::signed(BOB),
+			COLLECTION_ID_0,
+			TOKEN_ID_0,
+			SwapWant::Collection(COLLECTION_ID_0, Some((key, value)))
+		));
+		// The given NFT must have the wanted property
+		assert_noop!(
+			Marketplace::claim_swap(
+				Origin::signed(CHARLIE),
+				COLLECTION_ID_0,
+				TOKEN_ID_0,
+				Some((COLLECTION_ID_0, 1))
+			),
+			Error::<Test>::WrongNft
+		);
+		assert_ok!(Marketplace::claim_swap(
+			Origin::signed(CHARLIE),
+			COLLECTION_ID_0,
+			TOKEN_ID_0,
+			Some((COLLECTION_ID_0, 2))
+		));
+		assert_eq!(NFTCore::root_owner(COLLECTION_ID_0, TOKEN_ID_0).unwrap(), CHARLIE);
+		assert_eq!(NFTCore::root_owner(COLLECTION_ID_0, 2).unwrap(), BOB);
+	});
+}
+
+#[test]
+fn swap_property_set_by_root_owner_is_ignored() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_mint();
+		mint_to_charlie();
+		let key: BoundedVec<u8, KeyLimit> = stv("rarity").try_into().unwrap();
+		let value: BoundedVec<u8, ValueLimit> = stv("rare").try_into().unwrap();
+		assert_ok!(Marketplace::create_swap(
+			Origin::signed(BOB),
+			COLLECTION_ID_0,
+			TOKEN_ID_0,
+			SwapWant::Collection(COLLECTION_ID_0, Some((key.clone(), value.clone())))
+		));
+		// The root owner may set the property of the given NFT, but not on behalf of the issuer
+		assert_ok!(NFTCore::set_property(
+			Origin::signed(CHARLIE),
+			COLLECTION_ID_0,
+			Some(1),
+			key.clone(),
+			value.clone()
+		));
+		assert_noop!(
+			Marketplace::claim_swap(
+				Origin::signed(CHARLIE),
+				COLLECTION_ID_0,
+				TOKEN_ID_0,
+				Some((COLLECTION_ID_0, 1))
+			),
+			Error::<Test>::WrongNft
+		);
+		// A property set through the protocol origin is trusted like one set by the issuer
+		assert_ok!(NFTCore::set_property(Origin::root(), COLLECTION_ID_0, Some(1), key, value));
+		assert_ok!(Marketplace::claim_swap(
+			Origin::signed(CHARLIE),
+			COLLECTION_ID_0,
+			TOKEN_ID_0,
+			Some((COLLECTION_ID_0, 1))
+		));
+		assert_eq!(NFTCore::root_owner(COLLECTION_ID_0, 1).unwrap(), BOB);
+	});
+}
+
+#[test]
+fn swap_nft_for_balance_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_mint();
+		mint_to_charlie();
+		assert_ok!(Marketplace::create_swap(
+			Origin::signed(BOB),
+			COLLECTION_ID_0,
+			TOKEN_ID_0,
+			SwapWant::Balance(1_000)
+		));
+		assert_noop!(
+			Marketplace::claim_swap(
+				Origin::signed(CHARLIE),
+				COLLECTION_ID_0,
+				TOKEN_ID_0,
+				Some((COLLECTION_ID_0, 1))
+			),
+			Error::<Test>::WrongNft
+		);
+
+		let alice_balance = Balances::free_balance(ALICE);
+		let bob_balance = Balances::free_balance(BOB);
+		let charlie_balance = Balances::free_balance(CHARLIE);
+		assert_ok!(Marketplace::claim_swap(
+			Origin::signed(CHARLIE),
+			COLLECTION_ID_0,
+			TOKEN_ID_0,
+			None
+		));
+		// The author receives the royalty and the creator the rest of the balance
+		assert_eq!(Balances::free_balance(ALICE), alice_balance + 100);
+		assert_eq!(Balances::free_balance(BOB), bob_balance + 900);
+		assert_eq!(Balances::free_balance(CHARLIE), charlie_balance - 1_000);
+		assert_eq!(NFTCore::root_owner(COLLECTION_ID_0, TOKEN_ID_0).unwrap(), CHARLIE);
+	});
+}
@@ -0,0 +1,52 @@
+use frame_support::pallet_prelude::*;
+
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+use scale_info::TypeInfo;
+
+/// An NFT offered for sale at a fixed price.
+#[derive(Encode, Decode, Eq, Copy, PartialEq, Clone, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ListingInfo<AccountId, Balance, BlockNumber> {
+	/// The root owner of the NFT when it was listed, who receives the price
+	pub seller: AccountId,
+	/// The price of the NFT, including its royalty
+	pub price: Balance,
+	/// The block at which the listing expires, if any
+	pub expires: Option<BlockNumber>,
+}
+
+/// A bid on an NFT, whose amount is reserved from the bidder until it is accepted, withdrawn
+/// or expires.
+#[derive(Encode, Decode, Eq, Copy, PartialEq, Clone, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct OfferInfo<Balance, BlockNumber> {
+	/// The amount offered for the NFT, including its royalty
+	pub amount: Balance,
+	/// The block at which the offer expires and its amount is unreserved
+	pub expires: BlockNumber,
+}
+
+/// What the creator of a swap wants in exchange for the offered NFT.
+#[derive(Encode, Decode, Eq, PartialEq, Clone, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum SwapWant<CollectionId, NftId, BoundedKey, BoundedValue, Balance> {
+	/// A specific NFT
+	Nft(CollectionId, NftId),
+	/// Any NFT of a collection, optionally with a property set to a value by the collection
+	/// issuer
+	Collection(CollectionId, Option<(BoundedKey, BoundedValue)>),
+	/// An amount of balance, including the royalty of the offered NFT
+	Balance(Balance),
+}
+
+/// An NFT offered in exchange for an NFT or an amount of balance.
+#[derive(Encode, Decode, Eq, PartialEq, Clone, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct SwapInfo<AccountId, Want> {
+	/// The root owner of the offered NFT when the swap was created
+	pub creator: AccountId,
+	/// What the creator wants in exchange
+	pub want: Want,
+}
@@ -0,0 +1,106 @@
+//! Benchmarking setup for pallet-nft-core
+
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as NFTCore;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::traits::Get;
+use frame_system::RawOrigin;
+use pallet_uniques::DepositBalanceOf;
+use sp_runtime::traits::Bounded;
+
+const SEED: u32 = 0;
+
+/// Funds an account with enough balance for any deposit or price, which it can still receive
+/// payments on top of.
+fn fund<T: Config>(who: &T::AccountId) {
+	<T as pallet_uniques::Config>::Currency::make_free_balance_be(
+		who,
+		DepositBalanceOf::<T>::max_value() / 4u32.into(),
+	);
+}
+
+/// Creates a collection of a funded caller with the longest metadata allowed.
+fn create_collection<T: Config>() -> (T::CollectionId, T::AccountId) {
+	let caller: T::AccountId = whitelisted_caller();
+	fund::<T>(&caller);
+	let collection_id = NFTCore::<T>::next_collection_id();
+	assert!(NFTCore::<T>::create_collection(
+		RawOrigin::Signed(caller.clone()).into(),
+		vec![0; T::StringLimit::get() as usize],
+		None,
+		vec![0; T::StringLimit::get() as usize],
+		None,
+	)
+	.is_ok());
+	(collection_id, caller)
+}
+
+/// Creates a funded account which is not the collection issuer but allowlisted to mint into the
+/// collection for a price, so that its mints take the worst case path of paying the mint price
+/// and instance deposit to the collection owner.
+fn allowlisted_minter<T: Config>(
+	collection_id: T::CollectionId,
+	issuer: T::AccountId,
+) -> T::AccountId {
+	let minter: T::AccountId = account("minter", 0, SEED);
+	fund::<T>(&minter);
+	assert!(NFTCore::<T>::set_mint_settings(
+		RawOrigin::Signed(issuer.clone()).into(),
+		collection_id,
+		MintMode::Allowlist,
+		1_000u32.into(),
+	)
+	.is_ok());
+	assert!(NFTCore::<T>::set_mint_allowlist(
+		RawOrigin::Signed(issuer).into(),
+		collection_id,
+		vec![minter.clone()],
+		vec![],
+	)
+	.is_ok());
+	minter
+}
+
+benchmarks! {
+	mint_nft {
+		let (collection_id, caller) = create_collection::<T>();
+		let minter = allowlisted_minter::<T>(collection_id, caller);
+		let metadata = vec![0; T::StringLimit::get() as usize];
+		let royalty_recipients: RoyaltyRecipientsOf<T> = (0..T::MaxRoyaltyRecipients::get())
+			.map(|i| (account("royalty", i, SEED), Permill::from_parts(1)))
+			.collect::<Vec<_>>()
+			.try_into()
+			.unwrap();
+	}: _(
+		RawOrigin::Signed(minter.clone()),
+		collection_id,
+		None,
+		Some(minter.clone()),
+		Some(Permill::from_percent(10)),
+		Some(royalty_recipients),
+		Some(metadata),
+		true
+	)
+	verify {
+		assert!(NFTs::<T>::contains_key(collection_id, T::NftId::default()));
+	}
+
+	mint_nft_batch {
+		let n in 1 .. T::MaxBatchMint::get();
+		let (collection_id, caller) = create_collection::<T>();
+		let minter = allowlisted_minter::<T>(collection_id, caller);
+		let items: Vec<_> = (0..n)
+			.map(|i| {
+				let recipient: T::AccountId = account("recipient", i, SEED);
+				(recipient, Permill::from_percent(10), vec![0; T::StringLimit::get() as usize])
+			})
+			.collect();
+	}: _(RawOrigin::Signed(minter), collection_id, None, items)
+	verify {
+		assert_eq!(NFTCore::<T>::next_nft_id(collection_id), n.into());
+	}
+
+	impl_benchmark_test_suite!(NFTCore, crate::mock::ExtBuilder::default().build(), crate::mock::Test);
+}
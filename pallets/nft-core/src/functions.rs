@@ -0,0 +1,892 @@
+//! Various pieces of common functionality.
+
+use super::*;
+use codec::Encode;
+use frame_support::{
+	ensure,
+	storage::{with_transaction, TransactionOutcome},
+	traits::{ExistenceRequirement, Get},
+	weights::Weight,
+};
+use sp_runtime::{traits::Saturating, DispatchError, DispatchResult};
+
+impl<T: Config> Pallet<T> {
+	/// Returns the issuer of a collection, as recorded by `pallet_uniques`.
+	pub fn collection_issuer(
+		collection_id: T::CollectionId,
+	) -> Result<T::AccountId, DispatchError> {
+		ensure!(Collections::<T>::contains_key(collection_id), Error::<T>::CollectionUnknown);
+		pallet_uniques::Pallet::<T>::class_details(collection_id.into())
+			.map(|details| details.issuer)
+			.ok_or_else(|| Error::<T>::CollectionUnknown.into())
+	}
+
+	/// Returns the ancestors of an NFT, starting with its direct parent.
+	pub fn ancestors(
+		collection_id: T::CollectionId,
+		nft_id: T::NftId,
+	) -> Result<Vec<(T::CollectionId, T::NftId)>, DispatchError> {
+		let mut ancestors = Vec::new();
+		let mut current = (collection_id, nft_id);
+		while let Some(parent) = Parent::<T>::get(current.0, current.1) {
+			ensure!(
+				(ancestors.len() as u32) < T::MaxNestingDepth::get(),
+				Error::<T>::NestingTooDeep
+			);
+			ancestors.push(parent);
+			current = parent;
+		}
+		Ok(ancestors)
+	}
+
+	/// Returns the account owning the NFT at the top of the nesting tree of an NFT.
+	pub fn root_owner(
+		collection_id: T::CollectionId,
+		nft_id: T::NftId,
+	) -> Result<T::AccountId, DispatchError> {
+		let (root_collection_id, root_nft_id) =
+			Self::ancestors(collection_id, nft_id)?.pop().unwrap_or((collection_id, nft_id));
+		pallet_uniques::Pallet::<T>::owner(root_collection_id.into(), root_nft_id.into())
+			.ok_or_else(|| Error::<T>::NftUnknown.into())
+	}
+
+	/// Returns the NFTs nested under an NFT, directly or under its children.
+	pub fn descendants(
+		collection_id: T::CollectionId,
+		nft_id: T::NftId,
+	) -> Vec<(T::CollectionId, T::NftId)> {
+		let mut descendants = Vec::new();
+		let mut pending = Vec::new();
+		pending.push((collection_id, nft_id));
+		while let Some(current) = pending.pop() {
+			for child in Children::<T>::iter_key_prefix(current) {
+				descendants.push(child);
+				pending.push(child);
+			}
+		}
+		descendants
+	}
+
+	/// Returns the number of levels of NFTs nested under an NFT.
+	pub fn nesting_height(collection_id: T::CollectionId, nft_id: T::NftId) -> u32 {
+		Children::<T>::iter_key_prefix((collection_id, nft_id))
+			.map(|(child_collection_id, child_nft_id)| {
+				Self::nesting_height(child_collection_id, child_nft_id).saturating_add(1)
+			})
+			.max()
+			.unwrap_or(0)
+	}
+
+	/// Ensure that `sender` may set or remove properties of a collection or, if `maybe_nft_id`
+	/// is `Some`, of an NFT. Returns whether `sender` acts as the issuer or the root owner.
+	///
+	/// Only the collection issuer may manage collection properties, while NFT properties may be
+	/// managed by either the root owner of the NFT or the collection issuer.
+	pub(crate) fn ensure_can_set_property(
+		collection_id: T::CollectionId,
+		maybe_nft_id: Option<T::NftId>,
+		sender: &T::AccountId,
+	) -> Result<PropertySetter, DispatchError> {
+		let issuer = Self::collection_issuer(collection_id)?;
+		if sender == &issuer {
+			if let Some(nft_id) = maybe_nft_id {
+				ensure!(NFTs::<T>::contains_key(collection_id, nft_id), Error::<T>::NftUnknown);
+			}
+			return Ok(PropertySetter::Issuer)
+		}
+		let nft_id = maybe_nft_id.ok_or(Error::<T>::NoPermission)?;
+		ensure!(NFTs::<T>::contains_key(collection_id, nft_id), Error::<T>::NftUnknown);
+		ensure!(sender == &Self::root_owner(collection_id, nft_id)?, Error::<T>::NoPermission);
+		Ok(PropertySetter::Owner)
+	}
+
+	/// Returns the deposit for storing `length` bytes on top of `base`.
+	fn deposit_amount(base: BalanceOf<T>, length: usize) -> BalanceOf<T> {
+		T::DepositPerByte::get()
+			.saturating_mul((length as u32).into())
+			.saturating_add(base)
+	}
+
+	/// Reserve the deposit for storing `length` bytes on top of `base` from `maybe_depositor`.
+	/// No deposit is taken if `maybe_depositor` is `None`.
+	pub(crate) fn reserve_deposit(
+		maybe_depositor: Option<T::AccountId>,
+		base: BalanceOf<T>,
+		length: usize,
+	) -> Result<Option<DepositOf<T>>, DispatchError> {
+		match maybe_depositor {
+			Some(account) => {
+				let amount = Self::deposit_amount(base, length);
+				T::Currency::reserve(&account, amount)?;
+				Ok(Some(Deposit { account, amount }))
+			},
+			None => Ok(None),
+		}
+	}
+
+	/// Unreserve a deposit from the account which paid it, if one was taken.
+	pub(crate) fn unreserve_deposit(maybe_deposit: &Option<DepositOf<T>>) {
+		if let Some(deposit) = maybe_deposit {
+			T::Currency::unreserve(&deposit.account, deposit.amount);
+		}
+	}
+
+	/// Bring a deposit in line with the current deposit for storing `length` bytes on top of
+	/// `base`, reserving or unreserving the difference from the account which paid it.
+	///
+	/// If `maybe_check_depositor` is `Some`, deposits of other accounts are left untouched.
+	/// Deposits which were waived, e.g. for items created through the protocol origin, stay
+	/// waived, and deposits which cannot be raised because their depositor lacks the free
+	/// balance are left as they are. Returns whether the deposit changed.
+	pub(crate) fn do_redeposit(
+		maybe_check_depositor: Option<&T::AccountId>,
+		maybe_deposit: &mut Option<DepositOf<T>>,
+		base: BalanceOf<T>,
+		length: usize,
+	) -> bool {
+		let deposit = match maybe_deposit {
+			Some(deposit) => deposit,
+			None => return false,
+		};
+		if let Some(check_depositor) = maybe_check_depositor {
+			if &deposit.account != check_depositor {
+				return false
+			}
+		}
+		let amount = Self::deposit_amount(base, length);
+		if amount > deposit.amount {
+			if T::Currency::reserve(&deposit.account, amount - deposit.amount).is_err() {
+				return false
+			}
+		} else if amount < deposit.amount {
+			T::Currency::unreserve(&deposit.account, deposit.amount - amount);
+		} else {
+			return false
+		}
+		deposit.amount = amount;
+		true
+	}
+
+	/// Store a property set by `setter`, reserving its deposit from `maybe_depositor` and
+	/// refunding the deposit of the value it replaces. No deposit is taken if `maybe_depositor`
+	/// is `None`.
+	pub(crate) fn do_set_property(
+		collection_id: T::CollectionId,
+		maybe_nft_id: Option<T::NftId>,
+		key: BoundedVec<u8, T::KeyLimit>,
+		value: BoundedVec<u8, T::ValueLimit>,
+		setter: PropertySetter,
+		maybe_depositor: Option<T::AccountId>,
+	) -> DispatchResult {
+		let deposit = Self::reserve_deposit(
+			maybe_depositor,
+			T::AttributeDepositBase::get(),
+			key.len().saturating_add(value.len()),
+		)?;
+
+		if let Some((_, _, old_deposit)) = Properties::<T>::get((collection_id, maybe_nft_id, &key))
+		{
+			Self::unreserve_deposit(&old_deposit);
+		}
+		Properties::<T>::insert((collection_id, maybe_nft_id, &key), (value, setter, deposit));
+		Ok(())
+	}
+
+	/// Transfer `amount` from one account to another, keeping the payer alive. Nothing is
+	/// transferred if the amount is zero or both accounts are the same.
+	pub(crate) fn pay(
+		from: &T::AccountId,
+		to: &T::AccountId,
+		amount: BalanceOf<T>,
+	) -> DispatchResult {
+		if amount.is_zero() || from == to {
+			return Ok(())
+		}
+		T::Currency::transfer(from, to, amount, ExistenceRequirement::KeepAlive)
+	}
+
+	/// Reimburse the collection owner for the `pallet_uniques` instance deposit reserved from
+	/// them for each of `quantity` NFTs minted by `payer`, so that minting cannot tie up the
+	/// balance of the owner.
+	pub(crate) fn reimburse_instance_deposits(
+		class_details: &pallet_uniques::ClassDetailsFor<T, ()>,
+		payer: &T::AccountId,
+		quantity: u32,
+	) -> DispatchResult {
+		if class_details.free_holding {
+			return Ok(())
+		}
+		let amount = T::InstanceDeposit::get().saturating_mul(quantity.into());
+		Self::pay(payer, &class_details.owner, amount)
+	}
+
+	/// Ensure that `minter` may mint `quantity` NFTs into a collection under its mint mode and
+	/// pay the mint price of each of them to the collection owner, along with their instance
+	/// deposit.
+	///
+	/// The collection issuer may always mint, for free.
+	pub(crate) fn charge_mint(
+		collection_id: T::CollectionId,
+		minter: &T::AccountId,
+		quantity: u32,
+	) -> DispatchResult {
+		let collection = Self::collections(collection_id).ok_or(Error::<T>::CollectionUnknown)?;
+		let class_details = pallet_uniques::Pallet::<T>::class_details(collection_id.into())
+			.ok_or(Error::<T>::CollectionUnknown)?;
+		if minter == &class_details.issuer {
+			return Ok(())
+		}
+		match collection.mint_mode {
+			MintMode::Issuer => return Err(Error::<T>::NoPermission.into()),
+			MintMode::Public => (),
+			MintMode::Allowlist => ensure!(
+				MintAllowlist::<T>::contains_key(collection_id, minter),
+				Error::<T>::NoPermission
+			),
+		}
+		let price = collection.mint_price.saturating_mul(quantity.into());
+		Self::pay(minter, &class_details.owner, price)?;
+		Self::reimburse_instance_deposits(&class_details, minter, quantity)
+	}
+
+	/// Returns the leaf of a presale Merkle tree allowing `account` to mint `quantity` NFTs: the
+	/// `BlakeTwo256` hash of the SCALE encoded `(account, quantity)`.
+	pub fn presale_leaf(account: &T::AccountId, quantity: u32) -> MerkleHash {
+		BlakeTwo256::hash_of(&(account, quantity))
+	}
+
+	/// Returns whether `proof` proves that `leaf` is part of the Merkle tree with root `root`.
+	///
+	/// Each pair of nodes is hashed with `BlakeTwo256` in ascending order, so the proof only
+	/// consists of the sibling hashes from the leaf up to the root.
+	pub fn verify_merkle_proof(root: MerkleHash, leaf: MerkleHash, proof: &[MerkleHash]) -> bool {
+		let computed = proof.iter().fold(leaf, |node, sibling| {
+			if node <= *sibling {
+				BlakeTwo256::hash_of(&(node, sibling))
+			} else {
+				BlakeTwo256::hash_of(&(sibling, node))
+			}
+		});
+		computed == root
+	}
+
+	/// Returns the payload a mint voucher is signed over: the SCALE encoded tuple of the
+	/// `b"nft-core/voucher"` context, the genesis hash of the chain and the voucher, so that
+	/// signatures cannot be replayed on other chains or for other purposes.
+	pub fn voucher_payload(voucher: &MintVoucherOf<T>) -> Vec<u8> {
+		let genesis_hash = frame_system::Pallet::<T>::block_hash(T::BlockNumber::zero());
+		(b"nft-core/voucher", genesis_hash, voucher).encode()
+	}
+
+	/// Mint an NFT into a collection through `pallet_uniques` and store its nft-core data.
+	///
+	/// Fails if the royalty is not below 100%, its recipients' shares exceed 100% or the
+	/// collection has reached its maximum supply. Returns the id of the minted NFT.
+	pub(crate) fn do_mint_nft(
+		collection_id: T::CollectionId,
+		owner: T::AccountId,
+		nft: InstanceInfoOf<T>,
+	) -> Result<T::NftId, DispatchError> {
+		let collection = Self::collections(collection_id).ok_or(Error::<T>::CollectionUnknown)?;
+		ensure!(
+			!DestroyingCollections::<T>::contains_key(collection_id),
+			Error::<T>::CollectionBeingDestroyed
+		);
+		ensure!(nft.royalty < Permill::one(), Error::<T>::NotInRange);
+		if let Some(recipients) = &nft.royalty_recipients {
+			let total = recipients
+				.iter()
+				.fold(0u32, |total, (_, share)| total.saturating_add(share.deconstruct()));
+			ensure!(total <= Permill::one().deconstruct(), Error::<T>::RoyaltySharesTooHigh);
+		}
+
+		let nft_id =
+			NextNftId::<T>::try_mutate(collection_id, |id| -> Result<T::NftId, DispatchError> {
+				let current_id = *id;
+				if let Some(max) = collection.max {
+					ensure!(current_id < max.into(), Error::<T>::CollectionFullOrLocked);
+				}
+				*id = id.checked_add(&One::one()).ok_or(Error::<T>::NoAvailableNftId)?;
+				Ok(current_id)
+			})?;
+
+		pallet_uniques::Pallet::<T>::do_mint(
+			collection_id.into(),
+			nft_id.into(),
+			owner,
+			|_details| Ok(()),
+		)?;
+		NFTs::<T>::insert(collection_id, nft_id, nft);
+
+		Ok(nft_id)
+	}
+
+	/// Burn an NFT through `pallet_uniques`, refunding its deposit, and remove all of the
+	/// nft-core data stored alongside it. NFTs nested under it are burned as well.
+	///
+	/// If `maybe_check_owner` is `Some`, it must be either the root owner of the NFT or the
+	/// admin of its collection and of the collections of all NFTs nested under it. Returns the
+	/// root owner of the burned NFT.
+	pub(crate) fn do_burn_nft(
+		collection_id: T::CollectionId,
+		nft_id: T::NftId,
+		maybe_check_owner: Option<T::AccountId>,
+	) -> Result<T::AccountId, DispatchError> {
+		ensure!(NFTs::<T>::contains_key(collection_id, nft_id), Error::<T>::NftUnknown);
+		Self::ensure_not_locked(collection_id, nft_id)?;
+		let owner = Self::root_owner(collection_id, nft_id)?;
+		let maybe_check_admin = maybe_check_owner.filter(|check_owner| check_owner != &owner);
+		Self::ensure_can_burn_tree(collection_id, nft_id, maybe_check_admin.as_ref())?;
+
+		pallet_uniques::Pallet::<T>::do_burn(collection_id.into(), nft_id.into(), |_, _| Ok(()))?;
+		Self::remove_nft(collection_id, nft_id)?;
+
+		Ok(owner)
+	}
+
+	/// Ensure that neither an NFT nor any NFT it is nested under is locked, as a lock covers
+	/// the whole tree under the locked NFT.
+	pub(crate) fn ensure_not_locked(
+		collection_id: T::CollectionId,
+		nft_id: T::NftId,
+	) -> DispatchResult {
+		ensure!(!Locks::<T>::contains_key(collection_id, nft_id), Error::<T>::NftLocked);
+		for (ancestor_collection_id, ancestor_nft_id) in Self::ancestors(collection_id, nft_id)? {
+			ensure!(
+				!Locks::<T>::contains_key(ancestor_collection_id, ancestor_nft_id),
+				Error::<T>::NftLocked
+			);
+		}
+		Ok(())
+	}
+
+	/// Ensure that neither an NFT nor any NFT nested under it is locked, so that the whole tree
+	/// may be burned. If `maybe_check_admin` is `Some`, it must be the admin of the collection
+	/// of each of them.
+	pub(crate) fn ensure_can_burn_tree(
+		collection_id: T::CollectionId,
+		nft_id: T::NftId,
+		maybe_check_admin: Option<&T::AccountId>,
+	) -> DispatchResult {
+		let mut tree = Self::descendants(collection_id, nft_id);
+		tree.push((collection_id, nft_id));
+		for (tree_collection_id, tree_nft_id) in tree {
+			ensure!(
+				!Locks::<T>::contains_key(tree_collection_id, tree_nft_id),
+				Error::<T>::NftLocked
+			);
+			if let Some(check_admin) = maybe_check_admin {
+				let class_details =
+					pallet_uniques::Pallet::<T>::class_details(tree_collection_id.into())
+						.ok_or(Error::<T>::CollectionUnknown)?;
+				ensure!(&class_details.admin == check_admin, Error::<T>::NoPermission);
+			}
+		}
+		Ok(())
+	}
+
+	/// Remove the nft-core data of an NFT which has been burned in `pallet_uniques`, refunding
+	/// the deposits paid for it, and burn the NFTs nested under it.
+	fn remove_nft(collection_id: T::CollectionId, nft_id: T::NftId) -> DispatchResult {
+		if let Some(nft) = NFTs::<T>::take(collection_id, nft_id) {
+			Self::unreserve_deposit(&nft.deposit);
+		}
+		for (_, (_, deposit)) in Resources::<T>::drain_prefix((collection_id, nft_id)) {
+			Self::unreserve_deposit(&deposit);
+		}
+		Priorities::<T>::remove(collection_id, nft_id);
+		Locks::<T>::remove(collection_id, nft_id);
+		if let Some(equipping) = Equipped::<T>::take(collection_id, nft_id) {
+			Equippings::<T>::remove(equipping);
+		}
+		Equippings::<T>::remove_prefix((collection_id, nft_id), None);
+		for (_, (_, _, deposit)) in Properties::<T>::drain_prefix((collection_id, Some(nft_id))) {
+			Self::unreserve_deposit(&deposit);
+		}
+		if let Some(parent) = Parent::<T>::take(collection_id, nft_id) {
+			Children::<T>::remove(parent, (collection_id, nft_id));
+		}
+
+		let children: Vec<_> = Children::<T>::iter_key_prefix((collection_id, nft_id)).collect();
+		for (child_collection_id, child_nft_id) in children {
+			pallet_uniques::Pallet::<T>::do_burn(
+				child_collection_id.into(),
+				child_nft_id.into(),
+				|_, _| Ok(()),
+			)?;
+			Self::remove_nft(child_collection_id, child_nft_id)?;
+		}
+		Ok(())
+	}
+
+	/// Transfer an NFT to an account or nest it under another NFT.
+	///
+	/// Ownership is moved through `pallet_uniques`, keeping its `Account` and `Asset` storage
+	/// consistent. NFTs nested under the sent NFT are moved to its new root owner as well.
+	///
+	/// If `maybe_check_owner` is `Some`, it must be either the root owner of the NFT or its
+	/// approved delegate. Frozen and soulbound NFTs, NFTs which are locked or nested under a
+	/// locked NFT and frozen collections cannot be sent. Any approval is reset by the transfer.
+	/// Returns the previous root owner of the NFT.
+	pub fn do_send(
+		collection_id: T::CollectionId,
+		nft_id: T::NftId,
+		new_owner: AccountIdOrCollectionNftTupleOf<T>,
+		maybe_check_owner: Option<T::AccountId>,
+	) -> Result<T::AccountId, DispatchError> {
+		let nft = Self::nfts(collection_id, nft_id).ok_or(Error::<T>::NftUnknown)?;
+		ensure!(nft.transferable, Error::<T>::NonTransferable);
+		Self::ensure_not_locked(collection_id, nft_id)?;
+		ensure!(
+			!DestroyingCollections::<T>::contains_key(collection_id),
+			Error::<T>::CollectionBeingDestroyed
+		);
+		ensure!(
+			!Equipped::<T>::contains_key(collection_id, nft_id),
+			Error::<T>::CannotSendEquippedItem
+		);
+		let owner = Self::root_owner(collection_id, nft_id)?;
+
+		let dest = match new_owner {
+			AccountIdOrCollectionNftTuple::AccountId(ref account) => account.clone(),
+			AccountIdOrCollectionNftTuple::CollectionAndNftTuple(
+				parent_collection_id,
+				parent_nft_id,
+			) => {
+				ensure!(
+					NFTs::<T>::contains_key(parent_collection_id, parent_nft_id),
+					Error::<T>::NftUnknown
+				);
+				ensure!(
+					!DestroyingCollections::<T>::contains_key(parent_collection_id),
+					Error::<T>::CollectionBeingDestroyed
+				);
+				let mut ancestors = Self::ancestors(parent_collection_id, parent_nft_id)?;
+				ancestors.insert(0, (parent_collection_id, parent_nft_id));
+				ensure!(
+					!ancestors.contains(&(collection_id, nft_id)),
+					Error::<T>::CannotSendToDescendantOrSelf
+				);
+				let depth = (ancestors.len() as u32)
+					.saturating_add(Self::nesting_height(collection_id, nft_id));
+				ensure!(depth <= T::MaxNestingDepth::get(), Error::<T>::NestingTooDeep);
+				// Only a subtree joining another tree adds to the NFTs nested under its root
+				let root = *ancestors.last().unwrap_or(&(parent_collection_id, parent_nft_id));
+				if Self::ancestors(collection_id, nft_id)?.last() != Some(&root) {
+					let nested = Self::descendants(root.0, root.1)
+						.len()
+						.saturating_add(Self::descendants(collection_id, nft_id).len())
+						.saturating_add(1);
+					ensure!(nested as u32 <= T::MaxChildren::get(), Error::<T>::TooManyChildren);
+				}
+				Self::root_owner(parent_collection_id, parent_nft_id)?
+			},
+		};
+
+		pallet_uniques::Pallet::<T>::do_transfer(
+			collection_id.into(),
+			nft_id.into(),
+			dest.clone(),
+			|_class_details, details| {
+				if let Some(check_owner) = &maybe_check_owner {
+					if &details.owner != check_owner {
+						let approved =
+							details.approved.as_ref().map_or(false, |a| a == check_owner);
+						ensure!(approved, Error::<T>::NoPermission);
+					}
+				}
+				details.approved = None;
+				Ok(())
+			},
+		)?;
+		Self::transfer_children(collection_id, nft_id, &dest)?;
+
+		if let Some(parent) = Parent::<T>::take(collection_id, nft_id) {
+			Children::<T>::remove(parent, (collection_id, nft_id));
+		}
+		if let AccountIdOrCollectionNftTuple::CollectionAndNftTuple(
+			parent_collection_id,
+			parent_nft_id,
+		) = new_owner
+		{
+			Parent::<T>::insert(collection_id, nft_id, (parent_collection_id, parent_nft_id));
+			Children::<T>::insert(
+				(parent_collection_id, parent_nft_id),
+				(collection_id, nft_id),
+				(),
+			);
+		}
+
+		Ok(owner)
+	}
+
+	/// Equip an NFT into a slot of the NFT it is nested under.
+	///
+	/// The equipper must have an accepted resource composed from `base_id` which includes the
+	/// slot in its parts, while the resource `resource_id` of the item must be made for the slot
+	/// and the slot must whitelist the collection of the item. If `maybe_check_owner` is `Some`,
+	/// it must be the root owner of the equipper.
+	pub(crate) fn do_equip(
+		item: (T::CollectionId, T::NftId),
+		equipper: (T::CollectionId, T::NftId),
+		resource_id: T::ResourceId,
+		base_id: BaseId,
+		slot_id: SlotId,
+		maybe_check_owner: Option<T::AccountId>,
+	) -> DispatchResult {
+		let (item_collection_id, item_nft_id) = item;
+		let (equipper_collection_id, equipper_nft_id) = equipper;
+		ensure!(NFTs::<T>::contains_key(item_collection_id, item_nft_id), Error::<T>::NftUnknown);
+		ensure!(
+			Parent::<T>::get(item_collection_id, item_nft_id) == Some(equipper),
+			Error::<T>::MustBeDirectParent
+		);
+		if let Some(check_owner) = &maybe_check_owner {
+			ensure!(
+				check_owner == &Self::root_owner(equipper_collection_id, equipper_nft_id)?,
+				Error::<T>::NoPermission
+			);
+		}
+		ensure!(
+			!Equipped::<T>::contains_key(item_collection_id, item_nft_id),
+			Error::<T>::ItemAlreadyEquipped
+		);
+		ensure!(
+			!Equippings::<T>::contains_key((
+				equipper_collection_id,
+				equipper_nft_id,
+				base_id,
+				slot_id
+			)),
+			Error::<T>::SlotAlreadyEquipped
+		);
+
+		let (resource, _) = Self::resources((item_collection_id, item_nft_id, resource_id))
+			.ok_or(Error::<T>::ResourceUnknown)?;
+		ensure!(!resource.pending, Error::<T>::ResourcePending);
+		ensure!(
+			resource.base == Some(base_id) && resource.slot == Some(slot_id),
+			Error::<T>::ItemNotEquippable
+		);
+		let has_slot =
+			Resources::<T>::iter_prefix_values((equipper_collection_id, equipper_nft_id)).any(
+				|(resource, _)| {
+					!resource.pending &&
+						resource.base == Some(base_id) &&
+						resource.parts.map_or(false, |parts| parts.contains(&slot_id))
+				},
+			);
+		ensure!(has_slot, Error::<T>::EquipperHasNoSlot);
+
+		match Self::parts(base_id, slot_id).ok_or(Error::<T>::PartUnknown)? {
+			PartType::SlotPart(part) => {
+				let is_equippable = match part.equippable {
+					EquippableList::All => true,
+					EquippableList::Empty => false,
+					EquippableList::Custom(collections) =>
+						collections.contains(&item_collection_id),
+				};
+				ensure!(is_equippable, Error::<T>::CollectionNotEquippable);
+			},
+			PartType::FixedPart(_) => return Err(Error::<T>::PartNotSlot.into()),
+		}
+
+		Equippings::<T>::insert((equipper_collection_id, equipper_nft_id, base_id, slot_id), item);
+		Equipped::<T>::insert(
+			item_collection_id,
+			item_nft_id,
+			(equipper_collection_id, equipper_nft_id, base_id, slot_id),
+		);
+		Ok(())
+	}
+
+	/// Unequip what no longer fits once the resource `removed` is gone from an NFT.
+	///
+	/// The NFT is unequipped if none of its remaining accepted resources fits into the slot it is
+	/// equipped into, and so is any item equipped into a slot which none of them includes.
+	pub(crate) fn unequip_unfitting(
+		collection_id: T::CollectionId,
+		nft_id: T::NftId,
+		removed: &ResourceInfoOf<T>,
+	) {
+		let base_id = match removed.base {
+			Some(base_id) if !removed.pending => base_id,
+			_ => return,
+		};
+		let remaining: Vec<ResourceInfoOf<T>> =
+			Resources::<T>::iter_prefix_values((collection_id, nft_id))
+				.map(|(resource, _)| resource)
+				.filter(|resource| !resource.pending && resource.base == Some(base_id))
+				.collect();
+
+		if let Some(slot_id) = removed.slot {
+			let equipping = Equipped::<T>::get(collection_id, nft_id)
+				.filter(|&(_, _, equipped_base_id, equipped_slot_id)| {
+					equipped_base_id == base_id && equipped_slot_id == slot_id
+				});
+			if let Some(equipping) = equipping {
+				if !remaining.iter().any(|resource| resource.slot == Some(slot_id)) {
+					Equipped::<T>::remove(collection_id, nft_id);
+					Equippings::<T>::remove(equipping);
+					Self::deposit_event(Event::SlotUnequipped(
+						collection_id,
+						nft_id,
+						base_id,
+						slot_id,
+					));
+				}
+			}
+		}
+
+		for &slot_id in removed.parts.iter().flat_map(|parts| parts.iter()) {
+			let has_slot = remaining.iter().any(|resource| {
+				resource.parts.as_ref().map_or(false, |parts| parts.contains(&slot_id))
+			});
+			if has_slot {
+				continue
+			}
+			if let Some(item) = Equippings::<T>::take((collection_id, nft_id, base_id, slot_id)) {
+				Equipped::<T>::remove(item.0, item.1);
+				Self::deposit_event(Event::SlotUnequipped(item.0, item.1, base_id, slot_id));
+			}
+		}
+	}
+
+	/// Move the NFTs nested under an NFT to `dest` in `pallet_uniques`, so that they stay owned
+	/// by the root owner of their tree.
+	///
+	/// Fails if any of them is soulbound, locked or in a collection being destroyed.
+	fn transfer_children(
+		collection_id: T::CollectionId,
+		nft_id: T::NftId,
+		dest: &T::AccountId,
+	) -> DispatchResult {
+		for (child_collection_id, child_nft_id) in
+			Children::<T>::iter_key_prefix((collection_id, nft_id))
+		{
+			let child =
+				Self::nfts(child_collection_id, child_nft_id).ok_or(Error::<T>::NftUnknown)?;
+			ensure!(child.transferable, Error::<T>::NonTransferable);
+			ensure!(
+				!Locks::<T>::contains_key(child_collection_id, child_nft_id),
+				Error::<T>::NftLocked
+			);
+			ensure!(
+				!DestroyingCollections::<T>::contains_key(child_collection_id),
+				Error::<T>::CollectionBeingDestroyed
+			);
+			pallet_uniques::Pallet::<T>::do_transfer(
+				child_collection_id.into(),
+				child_nft_id.into(),
+				dest.clone(),
+				|_, details| {
+					details.approved = None;
+					Ok(())
+				},
+			)?;
+			Self::transfer_children(child_collection_id, child_nft_id, dest)?;
+		}
+		Ok(())
+	}
+
+	/// Destroy an empty collection, its class in `pallet_uniques` and the nft-core data stored
+	/// alongside it, refunding the class deposit to its owner and the metadata deposit to its
+	/// depositor.
+	pub(crate) fn do_destroy_collection(collection_id: T::CollectionId) -> DispatchResult {
+		let class_details = pallet_uniques::Pallet::<T>::class_details(collection_id.into())
+			.ok_or(Error::<T>::CollectionUnknown)?;
+		pallet_uniques::Pallet::<T>::do_destroy_class(
+			collection_id.into(),
+			class_details.destroy_witness(),
+			None,
+		)?;
+		if let Some(collection) = Collections::<T>::take(collection_id) {
+			Self::unreserve_deposit(&collection.deposit);
+		}
+		NextNftId::<T>::remove(collection_id);
+		PendingIssuer::<T>::remove(collection_id);
+		MintAllowlist::<T>::remove_prefix(collection_id, None);
+		Presales::<T>::remove(collection_id);
+		PresaleClaims::<T>::remove_prefix(collection_id, None);
+		DestroyingCollections::<T>::remove(collection_id);
+
+		Self::deposit_event(Event::CollectionBurned(class_details.owner, collection_id));
+		Ok(())
+	}
+
+	/// Remove the storage of collections marked for destruction by `burn_collection`, spending
+	/// at most `max_weight`. Returns the weight spent, which never exceeds `max_weight`.
+	///
+	/// Each step runs in its own storage transaction and burns a single NFT of a collection,
+	/// including any NFTs of the collection nested under it, or removes a single collection
+	/// property. NFTs of other collections nested under it are taken out of its tree and kept by
+	/// their root owner. Once nothing
+	/// is left the collection is destroyed. Locked NFTs are left alone, so a collection with
+	/// locked NFTs stays queued until they are unlocked. A collection whose step fails stays
+	/// queued as well, and `CollectionDestroyFailed` is emitted.
+	pub(crate) fn destroy_collections(max_weight: Weight) -> Weight {
+		// An upper bound of a step, which burns at most `MaxChildren` nested NFTs along with an
+		// NFT of the collection, in the same way as `burn_nft`, or takes them out of its tree.
+		let step_weight = 10_000 +
+			T::DbWeight::get().reads_writes(
+				T::MaxNestingDepth::get() as u64 + 11 * (T::MaxChildren::get() as u64 + 1) + 2,
+				16 * (T::MaxChildren::get() as u64 + 1),
+			);
+		// Only read as many collections as there is weight left for a step of each, plus the
+		// read which finds the end of the map.
+		let read_weight = T::DbWeight::get().reads(1);
+		let max_collections =
+			max_weight.saturating_sub(read_weight) / read_weight.saturating_add(step_weight);
+		if max_collections == 0 {
+			return 0
+		}
+		let collections: Vec<_> = DestroyingCollections::<T>::iter_keys()
+			.take(max_collections.try_into().unwrap_or(usize::MAX))
+			.collect();
+		let mut weight = read_weight.saturating_mul(collections.len() as u64 + 1);
+		for collection_id in collections {
+			// The number of NFTs of the collection skipped so far as they are locked
+			let mut locked = 0;
+			loop {
+				if weight.saturating_add(step_weight) > max_weight {
+					return weight
+				}
+				weight = weight.saturating_add(step_weight);
+
+				let result = with_transaction(|| {
+					match Self::destroy_collection_step(collection_id, &mut locked) {
+						Ok(more) => TransactionOutcome::Commit(Ok(more)),
+						Err(error) => TransactionOutcome::Rollback(Err(error)),
+					}
+				});
+				match result {
+					Ok(true) => continue,
+					Ok(false) => break,
+					Err(error) => {
+						Self::deposit_event(Event::CollectionDestroyFailed(collection_id, error));
+						break
+					},
+				}
+			}
+		}
+		weight
+	}
+
+	/// Remove the used vouchers which have expired, one expiry block after another from
+	/// `VoucherPruneCursor` on, spending at most `max_weight`. Returns the weight spent.
+	///
+	/// Each step removes at most `VOUCHER_PRUNE_LIMIT` vouchers, so that a block with many
+	/// expiring vouchers is pruned across several steps.
+	pub(crate) fn prune_vouchers(max_weight: Weight) -> Weight {
+		const VOUCHER_PRUNE_LIMIT: u32 = 100;
+		let step_weight =
+			10_000 + T::DbWeight::get().reads_writes(2, VOUCHER_PRUNE_LIMIT as u64 + 1);
+		let cursor_weight = T::DbWeight::get().reads_writes(1, 1);
+		if cursor_weight.saturating_add(step_weight) > max_weight {
+			return 0
+		}
+		let start = match VoucherPruneCursor::<T>::get() {
+			Some(cursor) => cursor,
+			// No voucher has been redeemed yet
+			None => return T::DbWeight::get().reads(1),
+		};
+		let now = frame_system::Pallet::<T>::block_number();
+		let mut cursor = start;
+		let mut weight = cursor_weight;
+		// Vouchers expire at their expiry block, so none can be redeemed for `cursor` anymore.
+		while cursor <= now && weight.saturating_add(step_weight) <= max_weight {
+			weight = weight.saturating_add(step_weight);
+			UsedVouchers::<T>::remove_prefix(cursor, Some(VOUCHER_PRUNE_LIMIT));
+			if UsedVouchers::<T>::iter_key_prefix(cursor).next().is_none() {
+				cursor = cursor.saturating_add(One::one());
+			}
+		}
+		if cursor != start {
+			VoucherPruneCursor::<T>::put(cursor);
+		}
+		weight
+	}
+
+	/// Take a single step in the destruction of a collection, skipping the first `locked` NFTs
+	/// of the collection and counting any further locked NFT found. Returns whether the
+	/// collection has more to be removed in this block.
+	fn destroy_collection_step(
+		collection_id: T::CollectionId,
+		locked: &mut usize,
+	) -> Result<bool, DispatchError> {
+		if let Some(nft_id) = NFTs::<T>::iter_key_prefix(collection_id).nth(*locked) {
+			let burnable = Self::ensure_not_locked(collection_id, nft_id)
+				.and_then(|_| Self::ensure_can_burn_tree(collection_id, nft_id, None));
+			match burnable {
+				Ok(()) => {
+					Self::unnest_foreign_descendants(collection_id, nft_id)?;
+					pallet_uniques::Pallet::<T>::do_burn(
+						collection_id.into(),
+						nft_id.into(),
+						|_, _| Ok(()),
+					)?;
+					Self::remove_nft(collection_id, nft_id)?;
+				},
+				Err(error) if error == Error::<T>::NftLocked.into() => *locked += 1,
+				Err(error) => return Err(error),
+			}
+			return Ok(true)
+		}
+		if *locked > 0 {
+			// Wait for the locked NFTs, whose properties must be kept until they are burned.
+			return Ok(false)
+		}
+		if let Some(((maybe_nft_id, key), (_, _, deposit))) =
+			Properties::<T>::iter_prefix((collection_id,)).next()
+		{
+			Properties::<T>::remove((collection_id, maybe_nft_id, key));
+			Self::unreserve_deposit(&deposit);
+			return Ok(true)
+		}
+		Self::do_destroy_collection(collection_id)?;
+		Ok(false)
+	}
+
+	/// Take the NFTs of other collections nested under an NFT of a collection being destroyed out
+	/// of its tree, so that they are kept by their root owner rather than burned along with it.
+	///
+	/// They are already owned by the root owner in `pallet_uniques`, so only their nesting is
+	/// removed. NFTs nested under them stay attached.
+	fn unnest_foreign_descendants(
+		collection_id: T::CollectionId,
+		nft_id: T::NftId,
+	) -> DispatchResult {
+		let children: Vec<_> = Children::<T>::iter_key_prefix((collection_id, nft_id)).collect();
+		for (child_collection_id, child_nft_id) in children {
+			if child_collection_id == collection_id {
+				Self::unnest_foreign_descendants(child_collection_id, child_nft_id)?;
+				continue
+			}
+			let owner = pallet_uniques::Pallet::<T>::owner(
+				child_collection_id.into(),
+				child_nft_id.into(),
+			)
+			.ok_or(Error::<T>::NftUnknown)?;
+			Parent::<T>::remove(child_collection_id, child_nft_id);
+			Children::<T>::remove((collection_id, nft_id), (child_collection_id, child_nft_id));
+			if let Some(equipping) = Equipped::<T>::take(child_collection_id, child_nft_id) {
+				let (_, _, base_id, slot_id) = equipping;
+				Equippings::<T>::remove(equipping);
+				Self::deposit_event(Event::SlotUnequipped(
+					child_collection_id,
+					child_nft_id,
+					base_id,
+					slot_id,
+				));
+			}
+
+			Self::deposit_event(Event::NFTSent(
+				owner.clone(),
+				AccountIdOrCollectionNftTuple::AccountId(owner),
+				child_collection_id,
+				child_nft_id,
+			));
+		}
+		Ok(())
+	}
+}
@@ -3,13 +3,34 @@
 #![allow(clippy::upper_case_acronyms)]
 
 use codec::HasCompact;
-use frame_support::{ensure, transactional, BoundedVec};
+use frame_support::{
+	ensure,
+	traits::{Currency, ReservableCurrency},
+	transactional, BoundedVec,
+};
 use frame_system::ensure_signed;
 
-use sp_runtime::traits::{AtLeast32BitUnsigned, CheckedAdd, One, StaticLookup, Zero};
+use sp_runtime::{
+	traits::{
+		AtLeast32BitUnsigned, BlakeTwo256, CheckedAdd, Hash as HashT, IdentifyAccount, One,
+		StaticLookup, Verify, Zero,
+	},
+	Permill,
+};
 use sp_std::{convert::TryInto, vec::Vec};
 
-use types::{ClassInfo, InstanceInfo};
+pub use weights::WeightInfo;
+
+use types::{
+	AccountIdOrCollectionNftTuple, BaseId, BaseInfo, ClassInfo, Deposit, EquippableList,
+	InstanceInfo, MintMode, MintVoucher, PartId, PartType, Presale, PropertySetter, ResourceInfo,
+	SlotId, ThemeProperty,
+};
+
+mod functions;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
 
 #[cfg(test)]
 mod mock;
@@ -17,15 +38,59 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
-// pub type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as
-// frame_system::Config>::AccountId>>::Balance;
-pub type ClassInfoOf<T> = ClassInfo<BoundedVec<u8, <T as pallet_uniques::Config>::StringLimit>>;
+pub type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+pub type ClassInfoOf<T> = ClassInfo<
+	BoundedVec<u8, <T as pallet_uniques::Config>::StringLimit>,
+	<T as frame_system::Config>::AccountId,
+	BalanceOf<T>,
+>;
+pub type RoyaltyRecipientsOf<T> = BoundedVec<
+	(<T as frame_system::Config>::AccountId, Permill),
+	<T as Config>::MaxRoyaltyRecipients,
+>;
 pub type InstanceInfoOf<T> = InstanceInfo<
 	<T as frame_system::Config>::AccountId,
 	BoundedVec<u8, <T as pallet_uniques::Config>::StringLimit>,
+	RoyaltyRecipientsOf<T>,
+	BalanceOf<T>,
+>;
+pub type AccountIdOrCollectionNftTupleOf<T> = AccountIdOrCollectionNftTuple<
+	<T as frame_system::Config>::AccountId,
+	<T as Config>::CollectionId,
+	<T as Config>::NftId,
+>;
+pub type ResourceInfoOf<T> = ResourceInfo<
+	BoundedVec<u8, <T as pallet_uniques::Config>::StringLimit>,
+	BoundedVec<PartId, <T as Config>::PartsLimit>,
+>;
+pub type BaseInfoOf<T> = BaseInfo<
+	<T as frame_system::Config>::AccountId,
+	BoundedVec<u8, <T as pallet_uniques::Config>::StringLimit>,
+	BalanceOf<T>,
+>;
+pub type PartTypeOf<T> = PartType<
+	BoundedVec<u8, <T as pallet_uniques::Config>::StringLimit>,
+	BoundedVec<<T as Config>::CollectionId, <T as Config>::MaxCollectionsEquippablePerPart>,
+>;
+pub type ThemePropertyOf<T> = ThemeProperty<
+	BoundedVec<u8, <T as pallet_uniques::Config>::KeyLimit>,
+	BoundedVec<u8, <T as pallet_uniques::Config>::ValueLimit>,
+>;
+pub type DepositOf<T> = Deposit<<T as frame_system::Config>::AccountId, BalanceOf<T>>;
+pub type MerkleHash = <BlakeTwo256 as HashT>::Output;
+pub type PresaleOf<T> = Presale<MerkleHash, <T as frame_system::Config>::BlockNumber>;
+pub type MintVoucherOf<T> = MintVoucher<
+	<T as Config>::CollectionId,
+	BoundedVec<u8, <T as pallet_uniques::Config>::StringLimit>,
+	BalanceOf<T>,
+	<T as frame_system::Config>::BlockNumber,
 >;
 
+pub mod migrations;
+pub mod traits;
 pub mod types;
+pub mod weights;
 
 // Re-export pallet items so that they can be accessed from the crate namespace.
 pub use pallet::*;
@@ -43,12 +108,20 @@ pub mod pallet {
 		/// Because this pallet emits events, it depends on the runtime's definition of an event.
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 
+		/// The currency mechanism, used for paying for deposits. It shares its balance type with
+		/// `pallet_uniques` so that its deposit constants apply.
+		type Currency: ReservableCurrency<
+			Self::AccountId,
+			Balance = pallet_uniques::DepositBalanceOf<Self>,
+		>;
+
 		type CollectionId: Member
 			+ Parameter
 			+ Default
 			+ Copy
 			+ HasCompact
 			+ AtLeast32BitUnsigned
+			+ From<Self::ClassId>
 			+ Into<Self::ClassId>;
 
 		type ProtocolOrigin: EnsureOrigin<Self::Origin>;
@@ -63,6 +136,54 @@ pub mod pallet {
 			+ Into<Self::InstanceId>;
 
 		type ResourceId: Member + Parameter + Default + Copy + HasCompact + AtLeast32BitUnsigned;
+
+		/// The maximum number of ancestors a nested NFT may have.
+		#[pallet::constant]
+		type MaxNestingDepth: Get<u32>;
+
+		/// The maximum number of NFTs nested under an NFT, directly or under its children. This
+		/// bounds the work of sending or burning an NFT along with its nesting tree.
+		#[pallet::constant]
+		type MaxChildren: Get<u32>;
+
+		/// The maximum number of parts of a base, and of base parts a resource may be composed
+		/// of.
+		#[pallet::constant]
+		type PartsLimit: Get<u32>;
+
+		/// The maximum number of collections a slot part may whitelist for equipping.
+		#[pallet::constant]
+		type MaxCollectionsEquippablePerPart: Get<u32>;
+
+		/// The maximum number of properties of a base theme.
+		#[pallet::constant]
+		type MaxPropertiesPerTheme: Get<u32>;
+
+		/// The maximum number of NFTs minted by a single `mint_nft_batch` call.
+		#[pallet::constant]
+		type MaxBatchMint: Get<u32>;
+
+		/// The maximum number of hashes in the Merkle proof of a presale mint.
+		#[pallet::constant]
+		type MaxProofLength: Get<u32>;
+
+		/// The signature with which issuers sign mint vouchers off-chain.
+		type OffchainSignature: Verify<Signer = Self::OffchainPublic> + Parameter;
+
+		/// The public key of the signer of a mint voucher, identifying its account.
+		type OffchainPublic: IdentifyAccount<AccountId = Self::AccountId>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+
+		/// The maximum number of resources in the priority list of an NFT.
+		#[pallet::constant]
+		type MaxPriorities: Get<u32>;
+
+		/// The maximum number of accounts the royalty of an NFT may be split between, besides
+		/// its author.
+		#[pallet::constant]
+		type MaxRoyaltyRecipients: Get<u32>;
 	}
 
 	/// Next available collection ID.
@@ -81,6 +202,48 @@ pub mod pallet {
 	/// Stores collections info
 	pub type Collections<T: Config> = StorageMap<_, Twox64Concat, T::CollectionId, ClassInfoOf<T>>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn mint_allowlist)]
+	/// Stores the accounts which may mint into collections with `MintMode::Allowlist`
+	pub type MintAllowlist<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::CollectionId, Blake2_128Concat, T::AccountId, ()>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn presales)]
+	/// Stores the presale phase of a collection
+	pub type Presales<T: Config> = StorageMap<_, Twox64Concat, T::CollectionId, PresaleOf<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn presale_claims)]
+	/// Stores the number of NFTs each account has minted in the presales of a collection
+	pub type PresaleClaims<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::CollectionId,
+		Blake2_128Concat,
+		T::AccountId,
+		u32,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn used_vouchers)]
+	/// Stores the collection and nonce of the redeemed mint vouchers by their expiry, so that
+	/// they can be pruned once expired
+	pub type UsedVouchers<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::BlockNumber, Twox64Concat, (T::CollectionId, u64), ()>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn voucher_prune_cursor)]
+	/// Stores the next expiry block whose used vouchers are pruned, set when the first voucher is
+	/// redeemed
+	pub type VoucherPruneCursor<T: Config> = StorageValue<_, T::BlockNumber, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn pending_issuer)]
+	/// Stores the account a collection's issuer role has been offered to
+	pub type PendingIssuer<T: Config> = StorageMap<_, Twox64Concat, T::CollectionId, T::AccountId>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn nfts)]
 	/// Stores nft info
@@ -93,14 +256,140 @@ pub mod pallet {
 		InstanceInfoOf<T>,
 	>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn parent)]
+	/// Stores the parent of a nested nft
+	pub type Parent<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::CollectionId,
+		Twox64Concat,
+		T::NftId,
+		(T::CollectionId, T::NftId),
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn children)]
+	/// Stores the children of an nft
+	pub type Children<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		(T::CollectionId, T::NftId),
+		Twox64Concat,
+		(T::CollectionId, T::NftId),
+		(),
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn properties)]
+	/// Stores the properties of collections and nfts, along with who set them and the deposit
+	/// paid for them
+	pub type Properties<T: Config> = StorageNMap<
+		_,
+		(
+			NMapKey<Twox64Concat, T::CollectionId>,
+			NMapKey<Twox64Concat, Option<T::NftId>>,
+			NMapKey<Blake2_128Concat, BoundedVec<u8, T::KeyLimit>>,
+		),
+		(BoundedVec<u8, T::ValueLimit>, PropertySetter, Option<DepositOf<T>>),
+	>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn resources)]
-	/// Stores resource info
-	pub type Resources<T: Config> =
-		StorageDoubleMap<_, Twox64Concat, T::NftId, Twox64Concat, T::ResourceId, InstanceInfoOf<T>>;
+	/// Stores resource info, along with the deposit paid for it
+	pub type Resources<T: Config> = StorageNMap<
+		_,
+		(
+			NMapKey<Twox64Concat, T::CollectionId>,
+			NMapKey<Twox64Concat, T::NftId>,
+			NMapKey<Twox64Concat, T::ResourceId>,
+		),
+		(ResourceInfoOf<T>, Option<DepositOf<T>>),
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn priorities)]
+	/// Stores the order in which the resources of an nft should be rendered
+	pub type Priorities<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::CollectionId,
+		Twox64Concat,
+		T::NftId,
+		BoundedVec<T::ResourceId, T::MaxPriorities>,
+	>;
+
+	/// Next available base ID.
+	#[pallet::storage]
+	#[pallet::getter(fn next_base_id)]
+	pub type NextBaseId<T: Config> = StorageValue<_, BaseId, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn bases)]
+	/// Stores bases info
+	pub type Bases<T: Config> = StorageMap<_, Twox64Concat, BaseId, BaseInfoOf<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn parts)]
+	/// Stores the parts of a base
+	pub type Parts<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, BaseId, Twox64Concat, PartId, PartTypeOf<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn themes)]
+	/// Stores the named themes of a base, along with their deposit
+	pub type Themes<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		BaseId,
+		Blake2_128Concat,
+		BoundedVec<u8, T::StringLimit>,
+		(BoundedVec<ThemePropertyOf<T>, T::MaxPropertiesPerTheme>, Option<DepositOf<T>>),
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn equippings)]
+	/// Stores the nft equipped into each slot of an nft
+	pub type Equippings<T: Config> = StorageNMap<
+		_,
+		(
+			NMapKey<Twox64Concat, T::CollectionId>,
+			NMapKey<Twox64Concat, T::NftId>,
+			NMapKey<Twox64Concat, BaseId>,
+			NMapKey<Twox64Concat, SlotId>,
+		),
+		(T::CollectionId, T::NftId),
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn equipped)]
+	/// Stores the nft and slot an nft is equipped into
+	pub type Equipped<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::CollectionId,
+		Twox64Concat,
+		T::NftId,
+		(T::CollectionId, T::NftId, BaseId, SlotId),
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn locks)]
+	/// Stores the nfts locked by other pallets, e.g. while they are listed for sale
+	pub type Locks<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::CollectionId, Twox64Concat, T::NftId, ()>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn destroying_collections)]
+	/// Stores the collections being destroyed
+	pub type DestroyingCollections<T: Config> = StorageMap<_, Twox64Concat, T::CollectionId, ()>;
+
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
 
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	// Pallets use events to inform users when important changes are made.
@@ -110,20 +399,36 @@ pub mod pallet {
 	pub enum Event<T: Config> {
 		CollectionCreated(T::AccountId, T::CollectionId),
 		NftMinted(T::AccountId, T::CollectionId, T::NftId),
-		NFTBurned(T::AccountId, T::NftId),
+		NFTBurned(T::AccountId, T::CollectionId, T::NftId),
 		CollectionBurned(T::AccountId, T::CollectionId),
-		NFTSent(T::AccountId, T::AccountId, T::CollectionId, T::NftId),
+		NFTSent(T::AccountId, AccountIdOrCollectionNftTupleOf<T>, T::CollectionId, T::NftId),
+		IssuerChangeOffered(T::AccountId, T::AccountId, T::CollectionId),
 		IssuerChanged(T::AccountId, T::AccountId, T::CollectionId),
+		IssuerChangeCancelled(T::AccountId, T::AccountId, T::CollectionId),
 		PropertySet(
 			T::CollectionId,
 			Option<T::NftId>,
 			BoundedVec<u8, T::KeyLimit>,
 			BoundedVec<u8, T::ValueLimit>,
 		),
+		PropertyRemoved(T::CollectionId, Option<T::NftId>, BoundedVec<u8, T::KeyLimit>),
 		CollectionLocked(T::AccountId, T::CollectionId),
-		ResourceAdded(T::NftId, T::ResourceId),
-		ResourceAccepted(T::NftId, T::ResourceId),
+		ResourceAdded(T::CollectionId, T::NftId, T::ResourceId),
+		ResourceAccepted(T::CollectionId, T::NftId, T::ResourceId),
+		ResourceRejected(T::CollectionId, T::NftId, T::ResourceId),
+		ResourceRemoved(T::CollectionId, T::NftId, T::ResourceId),
 		PrioritySet(T::CollectionId, T::NftId),
+		RoyaltyPaid(T::CollectionId, T::NftId, BalanceOf<T>),
+		BaseCreated(T::AccountId, BaseId),
+		SlotEquipped(T::CollectionId, T::NftId, BaseId, SlotId),
+		SlotUnequipped(T::CollectionId, T::NftId, BaseId, SlotId),
+		ThemeAdded(BaseId, BoundedVec<u8, T::StringLimit>),
+		CollectionDestroyFailed(T::CollectionId, DispatchError),
+		Redeposited(T::CollectionId, Vec<T::NftId>, Vec<(T::NftId, T::ResourceId)>),
+		MintSettingsChanged(T::CollectionId, MintMode, BalanceOf<T>),
+		MintAllowlistChanged(T::CollectionId),
+		PresaleSet(T::CollectionId, PresaleOf<T>),
+		VoucherRedeemed(T::AccountId, T::CollectionId, T::NftId, u64),
 	}
 
 	// Errors inform users that something went wrong.
@@ -140,7 +445,60 @@ pub mod pallet {
 		NoAvailableNftId,
 		NotInRange,
 		RoyaltyNotSet,
-		CollectionUnknown
+		CollectionUnknown,
+		NftUnknown,
+		NoPermission,
+		CannotSendToDescendantOrSelf,
+		NestingTooDeep,
+		TooManyChildren,
+		NoPendingIssuer,
+		PropertyUnknown,
+		CollectionFullOrLocked,
+		ResourceAlreadyExists,
+		ResourceUnknown,
+		ResourceNotPending,
+		ResourcePending,
+		RoyaltySharesTooHigh,
+		NoAvailableBaseId,
+		PartAlreadyExists,
+		PartUnknown,
+		PartNotSlot,
+		CollectionNotEquippable,
+		MustBeDirectParent,
+		ItemNotEquippable,
+		EquipperHasNoSlot,
+		SlotAlreadyEquipped,
+		ItemAlreadyEquipped,
+		NotEquipped,
+		CannotSendEquippedItem,
+		BaseUnknown,
+		DuplicateThemeProperty,
+		NonTransferable,
+		IssuerNotSet,
+		TooManyItems,
+		CollectionBeingDestroyed,
+		RecipientNotSet,
+		OwnerNotSet,
+		PresaleUnknown,
+		PresaleNotActive,
+		InvalidProof,
+		PresaleAllowanceExceeded,
+		VoucherExpired,
+		VoucherAlreadyUsed,
+		InvalidSignature,
+		NftLocked,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			migrations::migrate_to_v1::<T>()
+		}
+
+		fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			let weight = Self::destroy_collections(remaining_weight);
+			weight.saturating_add(Self::prune_vouchers(remaining_weight.saturating_sub(weight)))
+		}
 	}
 
 	#[pallet::call]
@@ -148,78 +506,165 @@ pub mod pallet {
 		/// Mints an NFT in the specified collection
 		/// Sets metadata and the royalty attribute
 		///
+		/// A deposit of `MetadataDepositBase` plus `DepositPerByte` for each byte of the metadata
+		/// is reserved from the sender until the NFT is burned.
+		///
+		/// Unless the sender is the collection issuer, the mint mode of the collection must allow
+		/// the sender to mint and the mint price is paid to the collection owner, along with the
+		/// `pallet_uniques` instance deposit reserved from the owner for the NFT.
+		///
 		/// Parameters:
 		/// - `collection_id`: The class of the asset to be minted.
-		/// - `nft_id`: The nft value of the asset to be minted.
+		/// - `recipient`: The owner of the minted nft, the sender if `None`. Must be set when
+		///   minting through the protocol origin.
 		/// - `author`: Receiver of the royalty
-		/// - `royalty`: Percentage reward from each trade for the author
+		/// - `royalty`: Share of each sale paid as royalty, below 100%
+		/// - `royalty_recipients`: Accounts receiving a share of the royalty, the remainder goes
+		///   to the author
 		/// - `metadata`: Arbitrary data about an nft, e.g. IPFS hash
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
+		/// - `transferable`: Whether the nft may ever change owner, soulbound nfts can only be
+		///   burned
+		#[pallet::weight(<T as Config>::WeightInfo::mint_nft())]
 		#[transactional]
 		pub fn mint_nft(
 			origin: OriginFor<T>,
 			collection_id: T::CollectionId,
+			recipient: Option<T::AccountId>,
 			author: Option<T::AccountId>,
-			royalty: Option<u8>,
+			royalty: Option<Permill>,
+			royalty_recipients: Option<RoyaltyRecipientsOf<T>>,
 			metadata: Option<Vec<u8>>,
+			transferable: bool,
 		) -> DispatchResult {
 			let sender = match T::ProtocolOrigin::try_origin(origin) {
 				Ok(_) => None,
 				Err(origin) => Some(ensure_signed(origin)?),
 			};
 
-			let _ = Self::collections(collection_id)
-				.ok_or(Error::<T>::CollectionUnknown)?;
-
-			if let Some(r) = royalty {
-				ensure!(r < 100, Error::<T>::NotInRange);
+			let recipient =
+				recipient.or_else(|| sender.clone()).ok_or(Error::<T>::RecipientNotSet)?;
+			if let Some(sender) = &sender {
+				Self::charge_mint(collection_id, sender, 1)?;
 			}
+			let metadata_bounded =
+				Self::to_bounded_string(metadata.ok_or(Error::<T>::MetadataNotSet)?)?;
+			let author = author.ok_or(Error::<T>::AuthorNotSet)?;
+			let royalty = royalty.ok_or(Error::<T>::RoyaltyNotSet)?;
+			let deposit = Self::reserve_deposit(
+				sender,
+				T::MetadataDepositBase::get(),
+				metadata_bounded.len(),
+			)?;
 
-			let nft_id: T::NftId = NextNftId::<T>::try_mutate(
+			let nft_id = Self::do_mint_nft(
 				collection_id,
-				|id| -> Result<T::NftId, DispatchError> {
-					let current_id = *id;
-					*id = id.checked_add(&One::one()).ok_or(Error::<T>::NoAvailableNftId)?;
-					Ok(current_id)
+				recipient.clone(),
+				InstanceInfo {
+					author,
+					royalty,
+					royalty_recipients,
+					metadata: metadata_bounded,
+					transferable,
+					deposit,
 				},
 			)?;
 
-			pallet_uniques::Pallet::<T>::do_mint(
-				collection_id.into(),
-				nft_id.into(),
-				sender.clone().unwrap_or_default(),
-				|_details| Ok(()),
-			)?;
+			Self::deposit_event(Event::NftMinted(recipient, collection_id, nft_id));
 
-			let metadata_bounded =
-				Self::to_bounded_string(metadata.ok_or(Error::<T>::MetadataNotSet)?)?;
-			let author = author.ok_or(Error::<T>::AuthorNotSet)?;
-			let royalty = royalty.ok_or(Error::<T>::RoyaltyNotSet)?;
+			Ok(())
+		}
 
-			NFTs::<T>::insert(
-				collection_id,
-				nft_id,
-				InstanceInfo { author, royalty, metadata: metadata_bounded },
-			);
+		/// Mints a batch of NFTs in the specified collection, either all of them or none
+		///
+		/// The sender pays the metadata deposit, instance deposit and mint price of each of them,
+		/// as with `mint_nft`.
+		///
+		/// Parameters:
+		/// - `collection_id`: The collection the NFTs are minted into.
+		/// - `author`: Receiver of the royalty of every NFT, the sender if `None`. Must be set
+		///   when minting through the protocol origin.
+		/// - `items`: The owner, royalty and metadata of each NFT, at most `MaxBatchMint` of
+		///   them.
+		#[pallet::weight(<T as Config>::WeightInfo::mint_nft_batch(items.len() as u32))]
+		#[transactional]
+		pub fn mint_nft_batch(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			author: Option<T::AccountId>,
+			items: Vec<(T::AccountId, Permill, Vec<u8>)>,
+		) -> DispatchResult {
+			let sender = match T::ProtocolOrigin::try_origin(origin) {
+				Ok(_) => None,
+				Err(origin) => Some(ensure_signed(origin)?),
+			};
 
-			Self::deposit_event(Event::NftMinted(
-				sender.unwrap_or_default(),
-				collection_id,
-				nft_id,
-			));
+			let author = author.or_else(|| sender.clone()).ok_or(Error::<T>::AuthorNotSet)?;
+			ensure!(items.len() as u32 <= T::MaxBatchMint::get(), Error::<T>::TooManyItems);
+			if let Some(sender) = &sender {
+				Self::charge_mint(collection_id, sender, items.len() as u32)?;
+			}
+			for (recipient, royalty, metadata) in items {
+				let metadata = Self::to_bounded_string(metadata)?;
+				let deposit = Self::reserve_deposit(
+					sender.clone(),
+					T::MetadataDepositBase::get(),
+					metadata.len(),
+				)?;
+				let nft_id = Self::do_mint_nft(
+					collection_id,
+					recipient.clone(),
+					InstanceInfo {
+						author: author.clone(),
+						royalty,
+						royalty_recipients: None,
+						metadata,
+						transferable: true,
+						deposit,
+					},
+				)?;
+				Self::deposit_event(Event::NftMinted(recipient, collection_id, nft_id));
+			}
 
 			Ok(())
 		}
 
 		/// Mint a collection
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
+		///
+		/// A deposit of `MetadataDepositBase` plus `DepositPerByte` for each byte of the metadata
+		/// and symbol is reserved from the sender on top of the `ClassDeposit` of the class, until
+		/// the collection is burned.
+		///
+		/// Parameters:
+		/// - `metadata`: Arbitrary data about the collection, e.g. IPFS hash
+		/// - `max`: The maximum number of NFTs which may ever be minted into the collection, if
+		///   any
+		/// - `symbol`: The ticker symbol of the collection
+		/// - `owner`: The owner and issuer of the collection, the sender if `None`. Only the
+		///   protocol origin may create a collection for another account, without a class
+		///   deposit.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4, 6))]
 		#[transactional]
-		pub fn create_collection(origin: OriginFor<T>, metadata: Vec<u8>) -> DispatchResult {
+		pub fn create_collection(
+			origin: OriginFor<T>,
+			metadata: Vec<u8>,
+			max: Option<u32>,
+			symbol: Vec<u8>,
+			owner: Option<T::AccountId>,
+		) -> DispatchResult {
 			let sender = match T::ProtocolOrigin::try_origin(origin) {
 				Ok(_) => None,
 				Err(origin) => Some(ensure_signed(origin)?),
 			};
 
+			let owner = owner.or_else(|| sender.clone()).ok_or(Error::<T>::OwnerNotSet)?;
+			let class_deposit = match &sender {
+				Some(sender) => {
+					ensure!(sender == &owner, Error::<T>::NoPermission);
+					T::ClassDeposit::get()
+				},
+				None => Zero::zero(),
+			};
+
 			let collection_id = NextCollectionId::<T>::try_mutate(
 				|id| -> Result<T::CollectionId, DispatchError> {
 					let current_id = *id;
@@ -229,107 +674,279 @@ pub mod pallet {
 			)?;
 
 			let metadata_bounded = Self::to_bounded_string(metadata)?;
+			let symbol_bounded = Self::to_bounded_string(symbol)?;
 
 			pallet_uniques::Pallet::<T>::do_create_class(
 				collection_id.into(),
-				sender.clone().unwrap_or_default(),
-				sender.clone().unwrap_or_default(),
-				T::ClassDeposit::get(),
+				owner.clone(),
+				owner.clone(),
+				class_deposit,
 				false,
-				pallet_uniques::Event::Created(
-					collection_id.into(),
-					sender.clone().unwrap_or_default(),
-					sender.clone().unwrap_or_default(),
-				),
+				pallet_uniques::Event::Created(collection_id.into(), owner.clone(), owner.clone()),
 			)?;
 
-			Collections::<T>::insert(collection_id, ClassInfo { metadata: metadata_bounded });
-
-			Self::deposit_event(Event::CollectionCreated(
-				sender.unwrap_or_default(),
+			let deposit = Self::reserve_deposit(
+				sender,
+				T::MetadataDepositBase::get(),
+				metadata_bounded.len().saturating_add(symbol_bounded.len()),
+			)?;
+			Collections::<T>::insert(
 				collection_id,
-			));
+				ClassInfo {
+					metadata: metadata_bounded,
+					max,
+					symbol: symbol_bounded,
+					mint_mode: MintMode::Issuer,
+					mint_price: Zero::zero(),
+					deposit,
+				},
+			);
+
+			Self::deposit_event(Event::CollectionCreated(owner, collection_id));
 			Ok(())
 		}
 
-		/// burn nft
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
+		/// Burns an NFT and removes its nft-core data and resources
+		///
+		/// Any NFTs nested under the burned NFT are burned along with it, so none of them may be
+		/// locked. A collection admin who is not the root owner may only burn the NFT if they are
+		/// the admin of the collection of every NFT nested under it as well.
+		///
+		/// Parameters:
+		/// - `collection_id`: The collection of the NFT to be burned.
+		/// - `nft_id`: The NFT to be burned, its root owner must be the sender or the sender
+		///   must be the collection admin.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(
+			T::MaxNestingDepth::get() as u64 + 9 * (T::MaxChildren::get() as u64 + 1),
+			12 * (T::MaxChildren::get() as u64 + 1),
+		))]
 		#[transactional]
-		pub fn burn_nft(origin: OriginFor<T>, nft_id: T::NftId) -> DispatchResult {
+		pub fn burn_nft(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			nft_id: T::NftId,
+		) -> DispatchResult {
 			let sender = match T::ProtocolOrigin::try_origin(origin) {
 				Ok(_) => None,
 				Err(origin) => Some(ensure_signed(origin)?),
 			};
-			// TODO
-			// pallet_uniques::Pallet::<T>::burn
-			Self::deposit_event(Event::NFTBurned(sender.unwrap_or_default(), nft_id));
+
+			let owner = Self::do_burn_nft(collection_id, nft_id, sender)?;
+
+			Self::deposit_event(Event::NFTBurned(owner, collection_id, nft_id));
 			Ok(())
 		}
 
-		/// burn collection
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
+		/// Destroys a collection, refunding its deposit to its owner
+		///
+		/// A collection without NFTs and properties is destroyed right away. Otherwise it is
+		/// marked as being destroyed, which stops any further mints and transfers, and its NFTs,
+		/// their nft-core data and its properties are removed in chunks whenever blocks have
+		/// weight left. `CollectionBurned` is emitted once the collection is gone. Locked NFTs
+		/// are only burned once they are unlocked, while NFTs of other collections nested under
+		/// its NFTs are taken out of their tree and kept by their root owner.
+		///
+		/// Parameters:
+		/// - `collection_id`: The collection to be destroyed, the sender must be its owner.
+		/// - `witness`: The destroy witness of the class of the collection in `pallet_uniques`.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(5,7) +
+			<<T as pallet_uniques::Config>::WeightInfo as pallet_uniques::WeightInfo>::destroy(
+				witness.instances,
+				witness.instance_metadatas,
+				witness.attributes,
+			))]
 		#[transactional]
 		pub fn burn_collection(
 			origin: OriginFor<T>,
 			collection_id: T::CollectionId,
+			witness: pallet_uniques::DestroyWitness,
 		) -> DispatchResult {
 			let sender = match T::ProtocolOrigin::try_origin(origin) {
 				Ok(_) => None,
 				Err(origin) => Some(ensure_signed(origin)?),
 			};
-			// TODO
-			Self::deposit_event(Event::CollectionBurned(sender.unwrap_or_default(), collection_id));
+
+			ensure!(Collections::<T>::contains_key(collection_id), Error::<T>::CollectionUnknown);
+			ensure!(
+				!DestroyingCollections::<T>::contains_key(collection_id),
+				Error::<T>::CollectionBeingDestroyed
+			);
+			let class_details = pallet_uniques::Pallet::<T>::class_details(collection_id.into())
+				.ok_or(Error::<T>::CollectionUnknown)?;
+			if let Some(sender) = &sender {
+				ensure!(sender == &class_details.owner, Error::<T>::NoPermission);
+			}
+			ensure!(
+				class_details.destroy_witness() == witness,
+				pallet_uniques::Error::<T>::BadWitness
+			);
+
+			let is_empty = NFTs::<T>::iter_key_prefix(collection_id).next().is_none() &&
+				Properties::<T>::iter_key_prefix((collection_id,)).next().is_none();
+			if is_empty {
+				Self::do_destroy_collection(collection_id)?;
+			} else {
+				DestroyingCollections::<T>::insert(collection_id, ());
+			}
 			Ok(())
 		}
 
-		/// transfer NFT from account A to account B
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
+		/// Transfers an NFT to an account or nests it under another NFT
+		///
+		/// Any NFTs nested under the sent NFT move along with it. The tree an NFT is nested into
+		/// may hold at most `MaxChildren` NFTs under its root.
+		///
+		/// Parameters:
+		/// - `collection_id`: The collection of the NFT to be sent.
+		/// - `nft_id`: The NFT to be sent, its root owner must be the sender or the sender must
+		///   be its approved delegate.
+		/// - `new_owner`: The account or the `(collection_id, nft_id)` of the NFT to receive
+		///   ownership of the NFT.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(
+			2 * T::MaxNestingDepth::get() as u64 + 6 * T::MaxChildren::get() as u64 + 3,
+			3 * T::MaxChildren::get() as u64 + 5,
+		))]
 		#[transactional]
 		pub fn send(
 			origin: OriginFor<T>,
 			collection_id: T::CollectionId,
 			nft_id: T::NftId,
-			dest: <T::Lookup as StaticLookup>::Source,
+			new_owner: AccountIdOrCollectionNftTupleOf<T>,
 		) -> DispatchResult {
 			let sender = match T::ProtocolOrigin::try_origin(origin) {
 				Ok(_) => None,
 				Err(origin) => Some(ensure_signed(origin)?),
 			};
-			let dest = T::Lookup::lookup(dest)?;
-			// TODO
-			Self::deposit_event(Event::NFTSent(
-				sender.unwrap_or_default(),
-				dest,
-				collection_id,
-				nft_id,
-			));
+
+			let owner = Self::do_send(collection_id, nft_id, new_owner.clone(), sender)?;
+
+			Self::deposit_event(Event::NFTSent(owner, new_owner, collection_id, nft_id));
 			Ok(())
 		}
 
-		/// changing the issuer of a collection or a base
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
+		/// Offers the issuer role of a collection to another account
+		///
+		/// The handover only takes effect once `new_issuer` calls `accept_issuer`, until then
+		/// either side may call `cancel_issuer_change`. As the collection and its deposit move to
+		/// the new issuer, only the owner of the collection may offer it, not an issuer the owner
+		/// delegated to through `pallet_uniques`.
+		///
+		/// Parameters:
+		/// - `collection_id`: The collection whose issuer is changed, the sender must be its
+		///   owner.
+		/// - `new_issuer`: The account offered the issuer role.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2,1))]
 		#[transactional]
 		pub fn change_issuer(
 			origin: OriginFor<T>,
 			collection_id: T::CollectionId,
-			dest: <T::Lookup as StaticLookup>::Source,
+			new_issuer: <T::Lookup as StaticLookup>::Source,
 		) -> DispatchResult {
 			let sender = match T::ProtocolOrigin::try_origin(origin) {
 				Ok(_) => None,
 				Err(origin) => Some(ensure_signed(origin)?),
 			};
-			let dest = T::Lookup::lookup(dest)?;
-			// TODO
-			Self::deposit_event(Event::IssuerChanged(
-				sender.unwrap_or_default(),
-				dest,
-				collection_id,
-			));
+			let new_issuer = T::Lookup::lookup(new_issuer)?;
+
+			let issuer = Self::collection_issuer(collection_id)?;
+			if let Some(sender) = &sender {
+				let class_details =
+					pallet_uniques::Pallet::<T>::class_details(collection_id.into())
+						.ok_or(Error::<T>::CollectionUnknown)?;
+				ensure!(sender == &class_details.owner, Error::<T>::NoPermission);
+			}
+			PendingIssuer::<T>::insert(collection_id, &new_issuer);
+
+			Self::deposit_event(Event::IssuerChangeOffered(issuer, new_issuer, collection_id));
+			Ok(())
+		}
+
+		/// Accepts the issuer role of a collection offered through `change_issuer`
+		///
+		/// Ownership of the collection and its deposit move to the sender, who also becomes
+		/// its issuer, admin and freezer.
+		///
+		/// Parameters:
+		/// - `collection_id`: The collection whose issuer role was offered to the sender.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3,3))]
+		#[transactional]
+		pub fn accept_issuer(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let new_issuer =
+				PendingIssuer::<T>::take(collection_id).ok_or(Error::<T>::NoPendingIssuer)?;
+			ensure!(sender == new_issuer, Error::<T>::NoPermission);
+			let issuer = Self::collection_issuer(collection_id)?;
+
+			pallet_uniques::Pallet::<T>::do_transfer_ownership(
+				collection_id.into(),
+				new_issuer.clone(),
+				None,
+			)?;
+			pallet_uniques::Pallet::<T>::do_set_team(
+				collection_id.into(),
+				new_issuer.clone(),
+				new_issuer.clone(),
+				new_issuer.clone(),
+				None,
+			)?;
+
+			Self::deposit_event(Event::IssuerChanged(issuer, new_issuer, collection_id));
+			Ok(())
+		}
+
+		/// Cancels a pending issuer change of a collection
+		///
+		/// Parameters:
+		/// - `collection_id`: The collection with a pending issuer change, the sender must be
+		///   either its owner or the account the role was offered to.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3,1))]
+		#[transactional]
+		pub fn cancel_issuer_change(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+		) -> DispatchResult {
+			let sender = match T::ProtocolOrigin::try_origin(origin) {
+				Ok(_) => None,
+				Err(origin) => Some(ensure_signed(origin)?),
+			};
+
+			let new_issuer =
+				PendingIssuer::<T>::take(collection_id).ok_or(Error::<T>::NoPendingIssuer)?;
+			let issuer = Self::collection_issuer(collection_id)?;
+			if let Some(sender) = &sender {
+				let class_details =
+					pallet_uniques::Pallet::<T>::class_details(collection_id.into())
+						.ok_or(Error::<T>::CollectionUnknown)?;
+				ensure!(
+					sender == &class_details.owner || sender == &new_issuer,
+					Error::<T>::NoPermission
+				);
+			}
+
+			Self::deposit_event(Event::IssuerChangeCancelled(issuer, new_issuer, collection_id));
 			Ok(())
 		}
 
-		/// set a custom value on an NFT
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
+		/// Sets a custom property on a collection or an NFT
+		///
+		/// A deposit of `AttributeDepositBase` plus `DepositPerByte` for each byte of the key and
+		/// value is reserved from the sender, replacing the deposit of any previous value.
+		///
+		/// Parameters:
+		/// - `collection_id`: The collection of the property, the sender must be its issuer
+		///   unless the property is set on an NFT.
+		/// - `maybe_nft_id`: The NFT of the property, if any, the sender must be either its root
+		///   owner or the collection issuer.
+		/// - `key`: The key of the property.
+		/// - `value`: The value to be stored under `key`.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(
+			T::MaxNestingDepth::get() as u64 + 3,
+			1,
+		))]
 		#[transactional]
 		pub fn set_property(
 			origin: OriginFor<T>,
@@ -342,13 +959,75 @@ pub mod pallet {
 				Ok(_) => None,
 				Err(origin) => Some(ensure_signed(origin)?),
 			};
-			// TODO
+			let setter = if let Some(sender) = &sender {
+				Self::ensure_can_set_property(collection_id, maybe_nft_id, sender)?
+			} else if let Some(nft_id) = maybe_nft_id {
+				ensure!(NFTs::<T>::contains_key(collection_id, nft_id), Error::<T>::NftUnknown);
+				PropertySetter::Protocol
+			} else {
+				ensure!(
+					Collections::<T>::contains_key(collection_id),
+					Error::<T>::CollectionUnknown
+				);
+				PropertySetter::Protocol
+			};
+
+			Self::do_set_property(
+				collection_id,
+				maybe_nft_id,
+				key.clone(),
+				value.clone(),
+				setter,
+				sender,
+			)?;
+
 			Self::deposit_event(Event::PropertySet(collection_id, maybe_nft_id, key, value));
 			Ok(())
 		}
 
-		/// lock collection
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
+		/// Removes a custom property from a collection or an NFT, refunding its deposit
+		///
+		/// Parameters:
+		/// - `collection_id`: The collection of the property, the sender must be its issuer
+		///   unless the property is set on an NFT.
+		/// - `maybe_nft_id`: The NFT of the property, if any, the sender must be either its root
+		///   owner or the collection issuer.
+		/// - `key`: The key of the property to be removed.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(
+			T::MaxNestingDepth::get() as u64 + 3,
+			1,
+		))]
+		#[transactional]
+		pub fn remove_property(
+			origin: OriginFor<T>,
+			#[pallet::compact] collection_id: T::CollectionId,
+			maybe_nft_id: Option<T::NftId>,
+			key: BoundedVec<u8, T::KeyLimit>,
+		) -> DispatchResult {
+			let sender = match T::ProtocolOrigin::try_origin(origin) {
+				Ok(_) => None,
+				Err(origin) => Some(ensure_signed(origin)?),
+			};
+
+			if let Some(sender) = &sender {
+				Self::ensure_can_set_property(collection_id, maybe_nft_id, sender)?;
+			}
+
+			let (_, _, deposit) = Properties::<T>::take((collection_id, maybe_nft_id, &key))
+				.ok_or(Error::<T>::PropertyUnknown)?;
+			Self::unreserve_deposit(&deposit);
+
+			Self::deposit_event(Event::PropertyRemoved(collection_id, maybe_nft_id, key));
+			Ok(())
+		}
+
+		/// Locks the supply of a collection at the number of NFTs minted so far
+		///
+		/// No further NFTs can be minted into a locked collection.
+		///
+		/// Parameters:
+		/// - `collection_id`: The collection to be locked, the sender must be its issuer.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3,1))]
 		#[transactional]
 		pub fn lock_collection(
 			origin: OriginFor<T>,
@@ -358,33 +1037,428 @@ pub mod pallet {
 				Ok(_) => None,
 				Err(origin) => Some(ensure_signed(origin)?),
 			};
-			// TODO
-			Self::deposit_event(Event::CollectionLocked(sender.unwrap_or_default(), collection_id));
+
+			let issuer = Self::collection_issuer(collection_id)?;
+			if let Some(sender) = &sender {
+				ensure!(sender == &issuer, Error::<T>::NoPermission);
+			}
+			let minted: u32 = Self::next_nft_id(collection_id)
+				.try_into()
+				.map_err(|_| Error::<T>::NotInRange)?;
+			Collections::<T>::try_mutate(collection_id, |collection| -> DispatchResult {
+				let collection = collection.as_mut().ok_or(Error::<T>::CollectionUnknown)?;
+				collection.max = Some(minted);
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::CollectionLocked(issuer, collection_id));
+			Ok(())
+		}
+
+		/// Sets who besides the issuer may mint NFTs into a collection and at which price
+		///
+		/// Parameters:
+		/// - `collection_id`: The collection, the sender must be its issuer.
+		/// - `mint_mode`: Who besides the issuer may mint.
+		/// - `mint_price`: The price of each NFT minted by others than the issuer, paid to the
+		///   collection owner.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2,1))]
+		#[transactional]
+		pub fn set_mint_settings(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			mint_mode: MintMode,
+			mint_price: BalanceOf<T>,
+		) -> DispatchResult {
+			let sender = match T::ProtocolOrigin::try_origin(origin) {
+				Ok(_) => None,
+				Err(origin) => Some(ensure_signed(origin)?),
+			};
+
+			let issuer = Self::collection_issuer(collection_id)?;
+			if let Some(sender) = &sender {
+				ensure!(sender == &issuer, Error::<T>::NoPermission);
+			}
+			Collections::<T>::try_mutate(collection_id, |collection| -> DispatchResult {
+				let collection = collection.as_mut().ok_or(Error::<T>::CollectionUnknown)?;
+				collection.mint_mode = mint_mode;
+				collection.mint_price = mint_price;
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::MintSettingsChanged(collection_id, mint_mode, mint_price));
+			Ok(())
+		}
+
+		/// Adds accounts to and removes accounts from the mint allowlist of a collection
+		///
+		/// The allowlist only applies while the collection uses `MintMode::Allowlist`.
+		///
+		/// Parameters:
+		/// - `collection_id`: The collection, the sender must be its issuer.
+		/// - `add`: The accounts to be allowed to mint.
+		/// - `remove`: The accounts no longer allowed to mint.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(
+			2,
+			add.len() as u64 + remove.len() as u64,
+		))]
+		#[transactional]
+		pub fn set_mint_allowlist(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			add: Vec<T::AccountId>,
+			remove: Vec<T::AccountId>,
+		) -> DispatchResult {
+			let sender = match T::ProtocolOrigin::try_origin(origin) {
+				Ok(_) => None,
+				Err(origin) => Some(ensure_signed(origin)?),
+			};
+
+			let issuer = Self::collection_issuer(collection_id)?;
+			if let Some(sender) = &sender {
+				ensure!(sender == &issuer, Error::<T>::NoPermission);
+			}
+			for account in add {
+				MintAllowlist::<T>::insert(collection_id, account, ());
+			}
+			for account in remove {
+				MintAllowlist::<T>::remove(collection_id, account);
+			}
+
+			Self::deposit_event(Event::MintAllowlistChanged(collection_id));
+			Ok(())
+		}
+
+		/// Opens a presale phase of a collection, replacing any previous one
+		///
+		/// The accounts of the Merkle tree may mint through `mint_with_proof` from the `start`
+		/// block until the `end` block. The number of NFTs minted by each account is tracked
+		/// across phases, so the quantity of a leaf is a total rather than a per phase amount.
+		///
+		/// Parameters:
+		/// - `collection_id`: The collection, the sender must be its issuer.
+		/// - `merkle_root`: The root of the Merkle tree of allowed `(account, quantity)` leaves,
+		///   see `presale_leaf` and `verify_merkle_proof`.
+		/// - `start`: The first block of the presale.
+		/// - `end`: The block at which the presale is over.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2,1))]
+		#[transactional]
+		pub fn set_presale(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			merkle_root: MerkleHash,
+			start: T::BlockNumber,
+			end: T::BlockNumber,
+		) -> DispatchResult {
+			let sender = match T::ProtocolOrigin::try_origin(origin) {
+				Ok(_) => None,
+				Err(origin) => Some(ensure_signed(origin)?),
+			};
+
+			let issuer = Self::collection_issuer(collection_id)?;
+			if let Some(sender) = &sender {
+				ensure!(sender == &issuer, Error::<T>::NoPermission);
+			}
+			ensure!(start < end, Error::<T>::NotInRange);
+			let presale = Presale { merkle_root, start, end };
+			Presales::<T>::insert(collection_id, presale);
+
+			Self::deposit_event(Event::PresaleSet(collection_id, presale));
+			Ok(())
+		}
+
+		/// Mints an NFT to the sender during the presale of a collection
+		///
+		/// The issuer becomes the author of the NFT without a royalty. The sender pays the mint
+		/// price of the collection, the instance deposit and the metadata deposit, as with
+		/// `mint_nft`.
+		///
+		/// Parameters:
+		/// - `collection_id`: The collection to mint from, its presale must be ongoing.
+		/// - `quantity`: The number of NFTs the sender may mint, as in its leaf of the Merkle
+		///   tree.
+		/// - `proof`: The sibling hashes from the leaf of the sender up to the Merkle root.
+		/// - `metadata`: Arbitrary data about the NFT, e.g. IPFS hash
+		#[pallet::weight(
+			<T as Config>::WeightInfo::mint_nft() + T::DbWeight::get().reads_writes(2, 1)
+		)]
+		#[transactional]
+		pub fn mint_with_proof(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			quantity: u32,
+			proof: BoundedVec<MerkleHash, T::MaxProofLength>,
+			metadata: Vec<u8>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let presale = Self::presales(collection_id).ok_or(Error::<T>::PresaleUnknown)?;
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(presale.start <= now && now < presale.end, Error::<T>::PresaleNotActive);
+			let leaf = Self::presale_leaf(&sender, quantity);
+			ensure!(
+				Self::verify_merkle_proof(presale.merkle_root, leaf, &proof),
+				Error::<T>::InvalidProof
+			);
+			PresaleClaims::<T>::try_mutate(collection_id, &sender, |claimed| {
+				ensure!(*claimed < quantity, Error::<T>::PresaleAllowanceExceeded);
+				*claimed += 1;
+				Ok::<(), Error<T>>(())
+			})?;
+
+			let collection =
+				Self::collections(collection_id).ok_or(Error::<T>::CollectionUnknown)?;
+			let class_details = pallet_uniques::Pallet::<T>::class_details(collection_id.into())
+				.ok_or(Error::<T>::CollectionUnknown)?;
+			Self::pay(&sender, &class_details.owner, collection.mint_price)?;
+			Self::reimburse_instance_deposits(&class_details, &sender, 1)?;
+
+			let metadata = Self::to_bounded_string(metadata)?;
+			let deposit = Self::reserve_deposit(
+				Some(sender.clone()),
+				T::MetadataDepositBase::get(),
+				metadata.len(),
+			)?;
+			let nft_id = Self::do_mint_nft(
+				collection_id,
+				sender.clone(),
+				InstanceInfo {
+					author: class_details.issuer,
+					royalty: Permill::zero(),
+					royalty_recipients: None,
+					metadata,
+					transferable: true,
+					deposit,
+				},
+			)?;
+
+			Self::deposit_event(Event::NftMinted(sender, collection_id, nft_id));
+			Ok(())
+		}
+
+		/// Mints an NFT to the sender from a voucher signed off-chain by the collection issuer
+		///
+		/// The issuer becomes the author of the NFT without a royalty and receives the price of
+		/// the voucher. The sender pays the instance deposit and the metadata deposit, as with
+		/// `mint_nft`. Each voucher can only be redeemed once, before it expires.
+		///
+		/// Parameters:
+		/// - `voucher`: The voucher to be redeemed.
+		/// - `signature`: The signature of the `voucher_payload` of the voucher by the collection
+		///   issuer.
+		#[pallet::weight(
+			<T as Config>::WeightInfo::mint_nft() + T::DbWeight::get().reads_writes(4, 3)
+		)]
+		#[transactional]
+		pub fn mint_with_voucher(
+			origin: OriginFor<T>,
+			voucher: MintVoucherOf<T>,
+			signature: T::OffchainSignature,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let collection_id = voucher.collection_id;
+			let issuer = Self::collection_issuer(collection_id)?;
+			ensure!(
+				frame_system::Pallet::<T>::block_number() < voucher.expiry,
+				Error::<T>::VoucherExpired
+			);
+			ensure!(
+				!UsedVouchers::<T>::contains_key(voucher.expiry, (collection_id, voucher.nonce)),
+				Error::<T>::VoucherAlreadyUsed
+			);
+			ensure!(
+				signature.verify(&Self::voucher_payload(&voucher)[..], &issuer),
+				Error::<T>::InvalidSignature
+			);
+			UsedVouchers::<T>::insert(voucher.expiry, (collection_id, voucher.nonce), ());
+			if !VoucherPruneCursor::<T>::exists() {
+				VoucherPruneCursor::<T>::put(frame_system::Pallet::<T>::block_number());
+			}
+
+			Self::pay(&sender, &issuer, voucher.price)?;
+			let class_details = pallet_uniques::Pallet::<T>::class_details(collection_id.into())
+				.ok_or(Error::<T>::CollectionUnknown)?;
+			Self::reimburse_instance_deposits(&class_details, &sender, 1)?;
+			let deposit = Self::reserve_deposit(
+				Some(sender.clone()),
+				T::MetadataDepositBase::get(),
+				voucher.metadata.len(),
+			)?;
+			let nft_id = Self::do_mint_nft(
+				collection_id,
+				sender.clone(),
+				InstanceInfo {
+					author: issuer,
+					royalty: Permill::zero(),
+					royalty_recipients: None,
+					metadata: voucher.metadata,
+					transferable: true,
+					deposit,
+				},
+			)?;
+
+			Self::deposit_event(Event::VoucherRedeemed(
+				sender,
+				collection_id,
+				nft_id,
+				voucher.nonce,
+			));
 			Ok(())
 		}
 
-		/// add resource
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
+		/// Adds a resource to an NFT
+		///
+		/// Resources added by the collection issuer to an NFT they do not own stay pending until
+		/// the root owner of the NFT calls `accept`, other resources are accepted right away.
+		///
+		/// A deposit of `MetadataDepositBase` plus `DepositPerByte` for each byte of the encoded
+		/// resource is reserved from the sender until the resource is removed.
+		///
+		/// Parameters:
+		/// - `collection_id`: The collection of the NFT.
+		/// - `nft_id`: The NFT to which the resource is added, the sender must be either its root
+		///   owner or the collection issuer.
+		/// - `resource_id`: The id of the resource, unique within the NFT.
+		/// - `resource`: The media and composition data of the resource, its `pending` flag is
+		///   set by the pallet.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(
+			T::MaxNestingDepth::get() as u64 + 4,
+			1,
+		))]
 		#[transactional]
 		pub fn add_resource(
 			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
 			nft_id: T::NftId,
 			resource_id: T::ResourceId,
+			mut resource: ResourceInfoOf<T>,
 		) -> DispatchResult {
 			let sender = match T::ProtocolOrigin::try_origin(origin) {
 				Ok(_) => None,
 				Err(origin) => Some(ensure_signed(origin)?),
 			};
-			// TODO, add resource_id
-			Self::deposit_event(Event::ResourceAdded(nft_id, resource_id));
+
+			let issuer = Self::collection_issuer(collection_id)?;
+			ensure!(NFTs::<T>::contains_key(collection_id, nft_id), Error::<T>::NftUnknown);
+			let owner = Self::root_owner(collection_id, nft_id)?;
+			if let Some(sender) = &sender {
+				ensure!(sender == &issuer || sender == &owner, Error::<T>::NoPermission);
+			}
+			resource.pending = sender.as_ref().map_or(false, |sender| sender != &owner);
+			ensure!(
+				!Resources::<T>::contains_key((collection_id, nft_id, resource_id)),
+				Error::<T>::ResourceAlreadyExists
+			);
+			let deposit = Self::reserve_deposit(
+				sender,
+				T::MetadataDepositBase::get(),
+				resource.encoded_size(),
+			)?;
+			Resources::<T>::insert((collection_id, nft_id, resource_id), (resource, deposit));
+
+			Self::deposit_event(Event::ResourceAdded(collection_id, nft_id, resource_id));
 			Ok(())
 		}
 
-		/// accept the addition of a new resource to an existing NFT
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
+		/// Accepts the addition of a pending resource to an NFT
+		///
+		/// Parameters:
+		/// - `collection_id`: The collection of the NFT.
+		/// - `nft_id`: The NFT of the resource, the sender must be its root owner.
+		/// - `resource_id`: The pending resource to be accepted.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(
+			T::MaxNestingDepth::get() as u64 + 2,
+			1,
+		))]
 		#[transactional]
 		pub fn accept(
 			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			nft_id: T::NftId,
+			resource_id: T::ResourceId,
+		) -> DispatchResult {
+			let sender = match T::ProtocolOrigin::try_origin(origin) {
+				Ok(_) => None,
+				Err(origin) => Some(ensure_signed(origin)?),
+			};
+
+			if let Some(sender) = &sender {
+				ensure!(
+					sender == &Self::root_owner(collection_id, nft_id)?,
+					Error::<T>::NoPermission
+				);
+			}
+			Resources::<T>::try_mutate((collection_id, nft_id, resource_id), |resource| {
+				let (resource, _) = resource.as_mut().ok_or(Error::<T>::ResourceUnknown)?;
+				ensure!(resource.pending, Error::<T>::ResourceNotPending);
+				resource.pending = false;
+				Ok::<(), Error<T>>(())
+			})?;
+
+			Self::deposit_event(Event::ResourceAccepted(collection_id, nft_id, resource_id));
+			Ok(())
+		}
+
+		/// Rejects the addition of a pending resource to an NFT, removing the resource
+		///
+		/// Parameters:
+		/// - `collection_id`: The collection of the NFT.
+		/// - `nft_id`: The NFT of the resource, the sender must be its root owner.
+		/// - `resource_id`: The pending resource to be rejected.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(
+			T::MaxNestingDepth::get() as u64 + 2,
+			1,
+		))]
+		#[transactional]
+		pub fn reject_resource(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			nft_id: T::NftId,
+			resource_id: T::ResourceId,
+		) -> DispatchResult {
+			let sender = match T::ProtocolOrigin::try_origin(origin) {
+				Ok(_) => None,
+				Err(origin) => Some(ensure_signed(origin)?),
+			};
+
+			if let Some(sender) = &sender {
+				ensure!(
+					sender == &Self::root_owner(collection_id, nft_id)?,
+					Error::<T>::NoPermission
+				);
+			}
+			let (resource, deposit) = Self::resources((collection_id, nft_id, resource_id))
+				.ok_or(Error::<T>::ResourceUnknown)?;
+			ensure!(resource.pending, Error::<T>::ResourceNotPending);
+			Resources::<T>::remove((collection_id, nft_id, resource_id));
+			Self::unreserve_deposit(&deposit);
+
+			Self::deposit_event(Event::ResourceRejected(collection_id, nft_id, resource_id));
+			Ok(())
+		}
+
+		/// Removes a resource from an NFT and from its priority list
+		///
+		/// If the NFT is equipped into a slot which none of its other resources fits into, it is
+		/// unequipped, and so are the items equipped into slots which none of its other resources
+		/// includes.
+		///
+		/// Parameters:
+		/// - `collection_id`: The collection of the NFT.
+		/// - `nft_id`: The NFT of the resource, the sender must be its root owner.
+		/// - `resource_id`: The resource to be removed.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(
+			T::MaxNestingDepth::get() as u64 +
+				T::MaxPriorities::get() as u64 +
+				T::PartsLimit::get() as u64 +
+				4,
+			2 * T::PartsLimit::get() as u64 + 4,
+		))]
+		#[transactional]
+		pub fn remove_resource(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
 			nft_id: T::NftId,
 			resource_id: T::ResourceId,
 		) -> DispatchResult {
@@ -392,25 +1466,321 @@ pub mod pallet {
 				Ok(_) => None,
 				Err(origin) => Some(ensure_signed(origin)?),
 			};
-			Self::deposit_event(Event::ResourceAccepted(nft_id, resource_id));
+
+			if let Some(sender) = &sender {
+				ensure!(
+					sender == &Self::root_owner(collection_id, nft_id)?,
+					Error::<T>::NoPermission
+				);
+			}
+			let (resource, deposit) = Resources::<T>::take((collection_id, nft_id, resource_id))
+				.ok_or(Error::<T>::ResourceUnknown)?;
+			Self::unreserve_deposit(&deposit);
+			Priorities::<T>::mutate_exists(collection_id, nft_id, |maybe_priorities| {
+				if let Some(priorities) = maybe_priorities {
+					priorities.retain(|id| id != &resource_id);
+					if priorities.is_empty() {
+						*maybe_priorities = None;
+					}
+				}
+			});
+			Self::unequip_unfitting(collection_id, nft_id, &resource);
+
+			Self::deposit_event(Event::ResourceRemoved(collection_id, nft_id, resource_id));
 			Ok(())
 		}
 
-		/// set a different order of resource priority
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
+		/// Sets the order in which the resources of an NFT should be rendered
+		///
+		/// Parameters:
+		/// - `collection_id`: The collection of the NFT.
+		/// - `nft_id`: The NFT whose resources are ordered, the sender must be its root owner.
+		/// - `priorities`: The accepted resources of the NFT, highest priority first.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(
+			T::MaxNestingDepth::get() as u64 + T::MaxPriorities::get() as u64 + 1,
+			1,
+		))]
 		#[transactional]
 		pub fn set_priority(
 			origin: OriginFor<T>,
 			collection_id: T::CollectionId,
 			nft_id: T::NftId,
+			priorities: BoundedVec<T::ResourceId, T::MaxPriorities>,
 		) -> DispatchResult {
 			let sender = match T::ProtocolOrigin::try_origin(origin) {
 				Ok(_) => None,
 				Err(origin) => Some(ensure_signed(origin)?),
 			};
+
+			if let Some(sender) = &sender {
+				ensure!(
+					sender == &Self::root_owner(collection_id, nft_id)?,
+					Error::<T>::NoPermission
+				);
+			} else {
+				ensure!(NFTs::<T>::contains_key(collection_id, nft_id), Error::<T>::NftUnknown);
+			}
+			for resource_id in priorities.iter() {
+				let (resource, _) = Self::resources((collection_id, nft_id, *resource_id))
+					.ok_or(Error::<T>::ResourceUnknown)?;
+				ensure!(!resource.pending, Error::<T>::ResourcePending);
+			}
+			Priorities::<T>::insert(collection_id, nft_id, priorities);
+
 			Self::deposit_event(Event::PrioritySet(collection_id, nft_id));
 			Ok(())
 		}
+
+		/// Creates a base which resources can be composed from
+		///
+		/// A deposit of `MetadataDepositBase` plus `DepositPerByte` for each byte of the base type,
+		/// symbol and encoded parts is reserved from the sender. Bases cannot be removed, so the
+		/// deposit stays reserved.
+		///
+		/// Parameters:
+		/// - `base_type`: The kind of media the base is made of, e.g. "svg"
+		/// - `symbol`: The ticker symbol of the base
+		/// - `parts`: The fixed and slot parts of the base, with unique ids
+		/// - `issuer`: The account managing the base, the sender if `None`. Only the protocol
+		///   origin may create a base for another account, without a deposit.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(
+			T::PartsLimit::get() as u64 + 2,
+			T::PartsLimit::get() as u64 + 3,
+		))]
+		#[transactional]
+		pub fn create_base(
+			origin: OriginFor<T>,
+			base_type: Vec<u8>,
+			symbol: Vec<u8>,
+			parts: BoundedVec<PartTypeOf<T>, T::PartsLimit>,
+			issuer: Option<T::AccountId>,
+		) -> DispatchResult {
+			let sender = match T::ProtocolOrigin::try_origin(origin) {
+				Ok(_) => None,
+				Err(origin) => Some(ensure_signed(origin)?),
+			};
+
+			let issuer = issuer.or_else(|| sender.clone()).ok_or(Error::<T>::IssuerNotSet)?;
+			if let Some(sender) = &sender {
+				ensure!(sender == &issuer, Error::<T>::NoPermission);
+			}
+			let base_type = Self::to_bounded_string(base_type)?;
+			let symbol = Self::to_bounded_string(symbol)?;
+			let length = base_type.len() + symbol.len() + parts.encoded_size();
+			let deposit = Self::reserve_deposit(sender, T::MetadataDepositBase::get(), length)?;
+
+			let base_id = NextBaseId::<T>::try_mutate(|id| -> Result<BaseId, DispatchError> {
+				let current_id = *id;
+				*id = id.checked_add(One::one()).ok_or(Error::<T>::NoAvailableBaseId)?;
+				Ok(current_id)
+			})?;
+
+			for part in parts {
+				ensure!(
+					!Parts::<T>::contains_key(base_id, part.id()),
+					Error::<T>::PartAlreadyExists
+				);
+				Parts::<T>::insert(base_id, part.id(), part);
+			}
+			Bases::<T>::insert(
+				base_id,
+				BaseInfo { issuer: issuer.clone(), base_type, symbol, deposit },
+			);
+
+			Self::deposit_event(Event::BaseCreated(issuer, base_id));
+			Ok(())
+		}
+
+		/// Equips an NFT into a slot of the NFT it is nested under
+		///
+		/// Parameters:
+		/// - `item`: The `(collection_id, nft_id)` of the NFT to be equipped, it must be nested
+		///   directly under `equipper`.
+		/// - `equipper`: The `(collection_id, nft_id)` of the NFT to equip `item` into, the sender
+		///   must be its root owner. One of its accepted resources must be composed from
+		///   `base_id` and include `slot_id` in its parts.
+		/// - `resource_id`: The accepted resource of `item` which fits into the slot.
+		/// - `base_id`: The base of the slot.
+		/// - `slot_id`: The slot part of the base, it must whitelist the collection of `item`.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(
+			T::MaxNestingDepth::get() as u64 + T::MaxPriorities::get() as u64 + 6,
+			2,
+		))]
+		#[transactional]
+		pub fn equip(
+			origin: OriginFor<T>,
+			item: (T::CollectionId, T::NftId),
+			equipper: (T::CollectionId, T::NftId),
+			resource_id: T::ResourceId,
+			base_id: BaseId,
+			slot_id: SlotId,
+		) -> DispatchResult {
+			let sender = match T::ProtocolOrigin::try_origin(origin) {
+				Ok(_) => None,
+				Err(origin) => Some(ensure_signed(origin)?),
+			};
+
+			Self::do_equip(item, equipper, resource_id, base_id, slot_id, sender)?;
+
+			Self::deposit_event(Event::SlotEquipped(item.0, item.1, base_id, slot_id));
+			Ok(())
+		}
+
+		/// Unequips an NFT from the slot it is equipped into
+		///
+		/// Parameters:
+		/// - `item`: The `(collection_id, nft_id)` of the equipped NFT, the sender must be its
+		///   root owner.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(
+			T::MaxNestingDepth::get() as u64 + 2,
+			2,
+		))]
+		#[transactional]
+		pub fn unequip(origin: OriginFor<T>, item: (T::CollectionId, T::NftId)) -> DispatchResult {
+			let sender = match T::ProtocolOrigin::try_origin(origin) {
+				Ok(_) => None,
+				Err(origin) => Some(ensure_signed(origin)?),
+			};
+
+			if let Some(sender) = &sender {
+				ensure!(sender == &Self::root_owner(item.0, item.1)?, Error::<T>::NoPermission);
+			}
+			let (equipper_collection_id, equipper_nft_id, base_id, slot_id) =
+				Equipped::<T>::take(item.0, item.1).ok_or(Error::<T>::NotEquipped)?;
+			Equippings::<T>::remove((equipper_collection_id, equipper_nft_id, base_id, slot_id));
+
+			Self::deposit_event(Event::SlotUnequipped(item.0, item.1, base_id, slot_id));
+			Ok(())
+		}
+
+		/// Adds a named theme to a base, replacing any theme of the same name
+		///
+		/// A deposit of `MetadataDepositBase` plus `DepositPerByte` for each byte of the name and
+		/// encoded properties is reserved from the sender, while the deposit of a replaced theme
+		/// is refunded to its depositor.
+		///
+		/// Parameters:
+		/// - `base_id`: The base of the theme, the sender must be its issuer.
+		/// - `name`: The name of the theme, e.g. "default" or "night"
+		/// - `properties`: The key/value pairs of the theme with unique keys, each flagged
+		///   whether parts equipped into the base inherit it.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3,3))]
+		#[transactional]
+		pub fn add_theme(
+			origin: OriginFor<T>,
+			base_id: BaseId,
+			name: BoundedVec<u8, T::StringLimit>,
+			properties: BoundedVec<ThemePropertyOf<T>, T::MaxPropertiesPerTheme>,
+		) -> DispatchResult {
+			let sender = match T::ProtocolOrigin::try_origin(origin) {
+				Ok(_) => None,
+				Err(origin) => Some(ensure_signed(origin)?),
+			};
+
+			let base = Self::bases(base_id).ok_or(Error::<T>::BaseUnknown)?;
+			if let Some(sender) = &sender {
+				ensure!(sender == &base.issuer, Error::<T>::NoPermission);
+			}
+			for (i, property) in properties.iter().enumerate() {
+				ensure!(
+					!properties[..i].iter().any(|other| other.key == property.key),
+					Error::<T>::DuplicateThemeProperty
+				);
+			}
+			if let Some((_, deposit)) = Themes::<T>::take(base_id, &name) {
+				Self::unreserve_deposit(&deposit);
+			}
+			let deposit = Self::reserve_deposit(
+				sender,
+				T::MetadataDepositBase::get(),
+				name.len().saturating_add(properties.encoded_size()),
+			)?;
+			Themes::<T>::insert(base_id, &name, (properties, deposit));
+
+			Self::deposit_event(Event::ThemeAdded(base_id, name));
+			Ok(())
+		}
+
+		/// Recalculates the deposits of a collection, its NFTs and their resources, e.g. after
+		/// `MetadataDepositBase` or `DepositPerByte` changed
+		///
+		/// The owner and admin of the collection, as well as the protocol origin, may recalculate
+		/// any of them, while other accounts may only recalculate the deposits they paid. Each
+		/// deposit stays with the account which paid it, which gets back or has reserved the
+		/// difference. Deposits which cannot be raised because their depositor lacks the free
+		/// balance are skipped.
+		///
+		/// Parameters:
+		/// - `collection_id`: The collection whose metadata deposit is recalculated.
+		/// - `nft_ids`: The NFTs of the collection whose metadata deposits are recalculated.
+		/// - `resources`: The `(nft_id, resource_id)` of the resources of the collection whose
+		///   deposits are recalculated.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(
+			2 * (1 + nft_ids.len() as u64 + resources.len() as u64) + 1,
+			2 * (1 + nft_ids.len() as u64 + resources.len() as u64),
+		))]
+		#[transactional]
+		pub fn redeposit(
+			origin: OriginFor<T>,
+			collection_id: T::CollectionId,
+			nft_ids: Vec<T::NftId>,
+			resources: Vec<(T::NftId, T::ResourceId)>,
+		) -> DispatchResult {
+			let sender = match T::ProtocolOrigin::try_origin(origin) {
+				Ok(_) => None,
+				Err(origin) => Some(ensure_signed(origin)?),
+			};
+
+			let mut collection =
+				Self::collections(collection_id).ok_or(Error::<T>::CollectionUnknown)?;
+			let class_details = pallet_uniques::Pallet::<T>::class_details(collection_id.into())
+				.ok_or(Error::<T>::CollectionUnknown)?;
+			let maybe_check_depositor = sender
+				.filter(|sender| sender != &class_details.owner && sender != &class_details.admin);
+			let maybe_check_depositor = maybe_check_depositor.as_ref();
+			let length = collection.metadata.len().saturating_add(collection.symbol.len());
+			let base = T::MetadataDepositBase::get();
+			if Self::do_redeposit(maybe_check_depositor, &mut collection.deposit, base, length) {
+				Collections::<T>::insert(collection_id, collection);
+			}
+
+			let mut redeposited_nfts = Vec::with_capacity(nft_ids.len());
+			for nft_id in nft_ids {
+				let mut nft = match Self::nfts(collection_id, nft_id) {
+					Some(nft) => nft,
+					None => continue,
+				};
+				let length = nft.metadata.len();
+				if Self::do_redeposit(maybe_check_depositor, &mut nft.deposit, base, length) {
+					NFTs::<T>::insert(collection_id, nft_id, nft);
+					redeposited_nfts.push(nft_id);
+				}
+			}
+
+			let mut redeposited_resources = Vec::with_capacity(resources.len());
+			for (nft_id, resource_id) in resources {
+				let (resource, mut deposit) =
+					match Self::resources((collection_id, nft_id, resource_id)) {
+						Some(resource) => resource,
+						None => continue,
+					};
+				let length = resource.encoded_size();
+				if Self::do_redeposit(maybe_check_depositor, &mut deposit, base, length) {
+					Resources::<T>::insert(
+						(collection_id, nft_id, resource_id),
+						(resource, deposit),
+					);
+					redeposited_resources.push((nft_id, resource_id));
+				}
+			}
+
+			Self::deposit_event(Event::Redeposited(
+				collection_id,
+				redeposited_nfts,
+				redeposited_resources,
+			));
+			Ok(())
+		}
 	}
 
 	impl<T: Config> Pallet<T> {
@@ -0,0 +1,74 @@
+//! Storage migrations of nft-core.
+
+use super::*;
+use codec::Decode;
+use frame_support::{
+	storage::migration::remove_storage_prefix,
+	traits::{Get, GetStorageVersion, PalletInfoAccess, StorageVersion},
+	weights::Weight,
+};
+
+/// The storage layout before version 1, in which collections and NFTs only stored their
+/// metadata and the royalty of an NFT was a whole percentage.
+pub mod v0 {
+	use super::*;
+
+	#[derive(Decode)]
+	pub struct ClassInfo<BoundedString> {
+		pub metadata: BoundedString,
+	}
+
+	#[derive(Decode)]
+	pub struct InstanceInfo<AccountId, BoundedString> {
+		pub author: AccountId,
+		pub royalty: u8,
+		pub metadata: BoundedString,
+	}
+
+	pub type ClassInfoOf<T> = ClassInfo<BoundedVec<u8, <T as pallet_uniques::Config>::StringLimit>>;
+	pub type InstanceInfoOf<T> = InstanceInfo<
+		<T as frame_system::Config>::AccountId,
+		BoundedVec<u8, <T as pallet_uniques::Config>::StringLimit>,
+	>;
+}
+
+/// Migrate the storage of nft-core from version 0 to version 1.
+///
+/// Collections keep their metadata and default to no maximum, an empty symbol and minting by
+/// the issuer only. NFTs keep their author and metadata, their royalty becomes a `Permill` and
+/// they stay transferable. As nothing was reserved for either of them, they have no deposit.
+/// Resources were keyed by NFT alone and stored the data of an NFT, so they cannot be carried
+/// over and are removed.
+pub fn migrate_to_v1<T: Config>() -> Weight {
+	if Pallet::<T>::on_chain_storage_version() >= 1 {
+		return T::DbWeight::get().reads(1)
+	}
+
+	let mut translated = 0u64;
+	Collections::<T>::translate::<v0::ClassInfoOf<T>, _>(|_, class| {
+		translated += 1;
+		Some(ClassInfo {
+			metadata: class.metadata,
+			max: None,
+			symbol: Default::default(),
+			mint_mode: MintMode::Issuer,
+			mint_price: Zero::zero(),
+			deposit: None,
+		})
+	});
+	NFTs::<T>::translate::<v0::InstanceInfoOf<T>, _>(|_, _, nft| {
+		translated += 1;
+		Some(InstanceInfo {
+			author: nft.author,
+			royalty: Permill::from_percent(nft.royalty.into()),
+			royalty_recipients: None,
+			metadata: nft.metadata,
+			transferable: true,
+			deposit: None,
+		})
+	});
+	remove_storage_prefix(Pallet::<T>::name().as_bytes(), b"Resources", &[]);
+
+	StorageVersion::new(1).put::<Pallet<T>>();
+	T::DbWeight::get().reads_writes(translated + 1, translated + 2)
+}
@@ -1,15 +1,16 @@
 use super::*;
 use crate as pallet_nft_core;
 
-use frame_support::traits::Everything;
+use frame_support::traits::{Everything, Get};
 use frame_support::{parameter_types, weights::Weight};
 use frame_system::EnsureRoot;
 use sp_core::{crypto::AccountId32, H256};
 use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentityLookup},
-	Perbill,
+	MultiSignature, MultiSigner, Perbill,
 };
+use sp_std::cell::RefCell;
 
 mod nfc {
 	// Re-export needed for `impl_outer_event!`.
@@ -38,15 +39,36 @@ frame_support::construct_runtime!(
 parameter_types! {
 	pub ClassBondAmount: Balance = 100;
 	pub MaxMetadataLength: u32 = 256;
+	pub const MaxNestingDepth: u32 = 3;
+	pub const MaxChildren: u32 = 4;
+	pub const PartsLimit: u32 = 3;
+	pub const MaxCollectionsEquippablePerPart: u32 = 2;
+	pub const MaxPropertiesPerTheme: u32 = 3;
+	pub const MaxBatchMint: u32 = 5;
+	pub const MaxPriorities: u32 = 5;
+	pub const MaxRoyaltyRecipients: u32 = 3;
+	pub const MaxProofLength: u32 = 8;
 }
 
 impl pallet_nft_core::Config for Test {
-	// type Currency = Balances;
+	type Currency = Balances;
 	type Event = Event;
 	type CollectionId = u32;
 	type NftId = u32;
 	type ResourceId = u32;
 	type ProtocolOrigin = EnsureRoot<AccountId>;
+	type MaxNestingDepth = MaxNestingDepth;
+	type MaxChildren = MaxChildren;
+	type PartsLimit = PartsLimit;
+	type MaxCollectionsEquippablePerPart = MaxCollectionsEquippablePerPart;
+	type MaxPropertiesPerTheme = MaxPropertiesPerTheme;
+	type MaxBatchMint = MaxBatchMint;
+	type WeightInfo = ();
+	type MaxPriorities = MaxPriorities;
+	type MaxRoyaltyRecipients = MaxRoyaltyRecipients;
+	type MaxProofLength = MaxProofLength;
+	type OffchainSignature = MultiSignature;
+	type OffchainPublic = MultiSigner;
 }
 
 parameter_types! {
@@ -74,6 +96,7 @@ impl pallet_uniques::Config for Test {
 	type StringLimit = UniquesStringLimit;
 	type KeyLimit = KeyLimit;
 	type ValueLimit = ValueLimit;
+	type Locker = NFTCore;
 	type WeightInfo = ();
 	// type InstanceReserveStrategy = NFT;
 }
@@ -111,8 +134,24 @@ impl frame_system::Config for Test {
 	type OnSetCode = ();
 }
 
+thread_local! {
+	static EXISTENTIAL_DEPOSIT: RefCell<Balance> = RefCell::new(1);
+}
+
+/// The existential deposit of `pallet_balances`, which tests may raise
+pub struct ExistentialDeposit;
+impl ExistentialDeposit {
+	pub fn set(amount: Balance) {
+		EXISTENTIAL_DEPOSIT.with(|v| *v.borrow_mut() = amount);
+	}
+}
+impl Get<Balance> for ExistentialDeposit {
+	fn get() -> Balance {
+		EXISTENTIAL_DEPOSIT.with(|v| *v.borrow())
+	}
+}
+
 parameter_types! {
-	pub const ExistentialDeposit: u64 = 1;
 	pub const MaxReserves: u32 = 50;
 }
 impl pallet_balances::Config for Test {
@@ -131,6 +170,7 @@ impl pallet_balances::Config for Test {
 pub const ALICE: AccountId = AccountId::new([1u8; 32]);
 pub const BOB: AccountId = AccountId::new([2u8; 32]);
 pub const CHARLIE: AccountId = AccountId::new([3u8; 32]);
+pub const DAVE: AccountId = AccountId::new([4u8; 32]);
 pub const RMRK: Balance = 1;
 pub const COLLECTION_ID_0: <Test as pallet_uniques::Config>::ClassId = 0;
 pub const COLLECTION_ID_1: <Test as pallet_uniques::Config>::ClassId = 1;
@@ -149,7 +189,11 @@ impl ExtBuilder {
 		let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
 
 		pallet_balances::GenesisConfig::<Test> {
-			balances: vec![(ALICE, 20_000 * RMRK), (BOB, 15_000 * RMRK), (CHARLIE, 150_000 * RMRK)],
+			balances: vec![
+				(ALICE, 200_000 * RMRK),
+				(BOB, 150_000 * RMRK),
+				(CHARLIE, 150_000 * RMRK),
+			],
 		}
 		.assimilate_storage(&mut t)
 		.unwrap();
@@ -1,10 +1,24 @@
-use frame_support::{assert_noop, assert_ok, error::BadOrigin};
+use frame_support::{
+	assert_noop, assert_ok,
+	error::BadOrigin,
+	storage::{unhashed, StoragePrefixedMap},
+	traits::{GetStorageVersion, Hooks, StorageVersion},
+	weights::Weight,
+};
 
 // use crate::types::ClassType;
 
 use super::*;
+use codec::Encode;
 use mock::*;
 use pallet_uniques as UNQ;
+use sp_core::{sr25519, Pair};
+use sp_runtime::{AccountId32, MultiSignature, MultiSigner, Permill};
+use traits::{NftLock, RoyaltyHandler};
+use types::{
+	AccountIdOrCollectionNftTuple::{AccountId as ToAccount, CollectionAndNftTuple as ToNft},
+	Deposit, FixedPart, MintMode, MintVoucher, SlotPart, ThemeProperty,
+};
 
 type NFTCore = Pallet<Test>;
 
@@ -13,26 +27,95 @@ fn stb(s: &str) -> BoundedVec<u8, ValueLimit> {
 	s.as_bytes().to_vec().try_into().unwrap()
 }
 
+/// Turns a string into a BoundedVec usable as a property key
+fn stbk(s: &str) -> BoundedVec<u8, KeyLimit> {
+	s.as_bytes().to_vec().try_into().unwrap()
+}
+
+/// Turns a string into a BoundedVec usable as a resource string
+fn stbs(s: &str) -> BoundedVec<u8, UniquesStringLimit> {
+	s.as_bytes().to_vec().try_into().unwrap()
+}
+
 /// Turns a string into a Vec
 fn stv(s: &str) -> Vec<u8> {
 	s.as_bytes().to_vec()
 }
 
+/// Creates a collection owned by ALICE
+fn basic_collection() {
+	assert_ok!(NFTCore::create_collection(
+		Origin::signed(ALICE),
+		stv("metadata"),
+		None,
+		stv("SYM"),
+		None
+	));
+}
+
+/// Mints an NFT owned by ALICE into the collection of ALICE
+fn basic_mint() {
+	assert_ok!(NFTCore::mint_nft(
+		Origin::signed(ALICE),
+		COLLECTION_ID_0,
+		None,
+		Some(ALICE),
+		Some(Permill::from_percent(0)),
+		None,
+		Some(stv("metadata")),
+		true
+	));
+}
+
+/// Returns the deposit for storing `length` bytes of metadata
+fn metadata_deposit(length: u64) -> BalanceOf<Test> {
+	UniquesMetadataDepositBase::get() + DepositPerByte::get() * length
+}
+
+/// Returns a resource pointing at some media
+fn basic_resource() -> ResourceInfoOf<Test> {
+	ResourceInfo {
+		src: Some(stbs("ipfs://src")),
+		metadata: Some(stbs("ipfs://metadata")),
+		license: None,
+		thumb: Some(stbs("ipfs://thumb")),
+		base: None,
+		parts: None,
+		slot: None,
+		pending: false,
+	}
+}
+
 #[test]
 fn create_collection_works() {
 	ExtBuilder::default().build().execute_with(|| {
         let metadata = stv("testing");
-		assert_ok!(NFTCore::create_collection(Origin::signed(ALICE), metadata.clone()));
+		assert_ok!(NFTCore::create_collection(
+			Origin::signed(ALICE),
+			metadata.clone(),
+			None,
+			stv("SYM"),
+			None
+		));
 		assert_noop!(
 			NFTCore::create_collection(
 				Origin::signed(ALICE),
-				vec![0; <Test as UNQ::Config>::StringLimit::get() as usize + 1]
+				vec![0; <Test as UNQ::Config>::StringLimit::get() as usize + 1],
+				None,
+				stv("SYM"),
+				None
 			),
 			Error::<Test>::TooLong
 		);
 		NextCollectionId::<Test>::mutate(|id| *id = <Test as UNQ::Config>::ClassId::max_value());
 		assert_noop!(
-			NFTCore::create_collection(Origin::signed(ALICE), metadata.clone()),
+			NFTCore::create_collection(
+				Origin::signed(ALICE),
+				metadata.clone(),
+				None,
+				stv("SYM"),
+				None
+			),
 			Error::<Test>::NoAvailableCollectionId
 		);        
 	});
@@ -41,37 +124,2230 @@ fn create_collection_works() {
 #[test]
 fn mint_nft_works() {
 	ExtBuilder::default().build().execute_with(|| {
-		assert_ok!(NFTCore::create_collection(Origin::signed(ALICE), b"metadata".to_vec()));
+		assert_ok!(NFTCore::create_collection(
+			Origin::signed(ALICE),
+			b"metadata".to_vec(),
+			None,
+			stv("SYM"),
+			None
+		));
 		assert_ok!(NFTCore::mint_nft(
 			Origin::signed(ALICE),
 			0,
+			None,
 			Some(ALICE),
-			Some(0),
-			Some(b"metadata".to_vec())
+			Some(Permill::from_percent(0)),
+			None,
+			Some(b"metadata".to_vec()),
+			true
 		));
 		assert_ok!(NFTCore::mint_nft(
 			Origin::signed(ALICE),
 			COLLECTION_ID_0,
+			None,
 			Some(ALICE),
-			Some(20),
-			Some(b"metadata".to_vec())
+			Some(Permill::from_percent(20)),
+			None,
+			Some(b"metadata".to_vec()),
+			true
 		));       
-        assert_ok!(NFTCore::mint_nft(
-            Origin::signed(BOB),
-            COLLECTION_ID_0,
-            Some(CHARLIE),
-            Some(20),
-            Some(b"metadata".to_vec())
-        ));
+		assert_noop!(
+			NFTCore::mint_nft(
+				Origin::signed(BOB),
+				COLLECTION_ID_0,
+				None,
+				Some(CHARLIE),
+				Some(Permill::from_percent(20)),
+				None,
+				Some(b"metadata".to_vec()),
+				true
+			),
+			Error::<Test>::NoPermission
+		);
 		assert_noop!(
 			NFTCore::mint_nft(
 				Origin::signed(ALICE),
 				NOT_EXISTING_CLASS_ID,
+				None,
 				Some(CHARLIE),
-				Some(20),
-				Some(b"metadata".to_vec())
+				Some(Permill::from_percent(20)),
+				None,
+				Some(b"metadata".to_vec()),
+				true
 			),
 			Error::<Test>::CollectionUnknown
 		);        
 	});
-}
\ No newline at end of file
+}
+
+#[test]
+fn burn_nft_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_collection();
+		basic_mint();
+		// The collection deposit covers "metadata" and "SYM", the NFT deposit "metadata"
+		assert_eq!(
+			Balances::reserved_balance(&ALICE),
+			ClassDeposit::get() +
+				metadata_deposit(11) +
+				InstanceDeposit::get() +
+				metadata_deposit(8)
+		);
+		assert_noop!(
+			NFTCore::burn_nft(Origin::signed(BOB), COLLECTION_ID_0, TOKEN_ID_0),
+			Error::<Test>::NoPermission
+		);
+		assert_ok!(NFTCore::burn_nft(Origin::signed(ALICE), COLLECTION_ID_0, TOKEN_ID_0));
+		assert!(NFTCore::nfts(COLLECTION_ID_0, TOKEN_ID_0).is_none());
+		assert!(UNQ::Pallet::<Test>::owner(COLLECTION_ID_0, TOKEN_ID_0).is_none());
+		assert_eq!(Balances::reserved_balance(&ALICE), ClassDeposit::get() + metadata_deposit(11));
+		assert_noop!(
+			NFTCore::burn_nft(Origin::signed(ALICE), COLLECTION_ID_0, TOKEN_ID_0),
+			Error::<Test>::NftUnknown
+		);
+	});
+}
+
+#[test]
+fn send_nft_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_collection();
+		basic_mint();
+		assert_noop!(
+			NFTCore::send(Origin::signed(BOB), COLLECTION_ID_0, TOKEN_ID_0, ToAccount(CHARLIE)),
+			Error::<Test>::NoPermission
+		);
+		assert_ok!(NFTCore::send(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			TOKEN_ID_0,
+			ToAccount(BOB)
+		));
+		assert_eq!(UNQ::Pallet::<Test>::owner(COLLECTION_ID_0, TOKEN_ID_0), Some(BOB));
+		// An approved delegate may send on behalf of the owner
+		assert_ok!(UNQ::Pallet::<Test>::approve_transfer(
+			Origin::signed(BOB),
+			COLLECTION_ID_0,
+			TOKEN_ID_0,
+			CHARLIE
+		));
+		assert_ok!(NFTCore::send(
+			Origin::signed(CHARLIE),
+			COLLECTION_ID_0,
+			TOKEN_ID_0,
+			ToAccount(CHARLIE)
+		));
+		assert_eq!(UNQ::Pallet::<Test>::owner(COLLECTION_ID_0, TOKEN_ID_0), Some(CHARLIE));
+		assert_noop!(
+			NFTCore::send(Origin::signed(ALICE), COLLECTION_ID_0, 42, ToAccount(BOB)),
+			Error::<Test>::NftUnknown
+		);
+	});
+}
+
+#[test]
+fn send_frozen_nft_fails() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_collection();
+		basic_mint();
+		assert_ok!(UNQ::Pallet::<Test>::freeze(Origin::signed(ALICE), COLLECTION_ID_0, TOKEN_ID_0));
+		assert_noop!(
+			NFTCore::send(Origin::signed(ALICE), COLLECTION_ID_0, TOKEN_ID_0, ToAccount(BOB)),
+			UNQ::Error::<Test>::Frozen
+		);
+		assert_ok!(UNQ::Pallet::<Test>::thaw(Origin::signed(ALICE), COLLECTION_ID_0, TOKEN_ID_0));
+		assert_ok!(UNQ::Pallet::<Test>::freeze_class(Origin::signed(ALICE), COLLECTION_ID_0));
+		assert_noop!(
+			NFTCore::send(Origin::signed(ALICE), COLLECTION_ID_0, TOKEN_ID_0, ToAccount(BOB)),
+			UNQ::Error::<Test>::Frozen
+		);
+	});
+}
+
+#[test]
+fn send_nft_to_nft_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_collection();
+		for _ in 0..3 {
+			basic_mint();
+		}
+		// Nest NFT 1 under NFT 0 and NFT 2 under NFT 1
+		assert_ok!(NFTCore::send(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			1,
+			ToNft(COLLECTION_ID_0, 0)
+		));
+		assert_ok!(NFTCore::send(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			2,
+			ToNft(COLLECTION_ID_0, 1)
+		));
+		assert_eq!(NFTCore::parent(COLLECTION_ID_0, 2), Some((COLLECTION_ID_0, 1)));
+		assert_eq!(NFTCore::children((COLLECTION_ID_0, 0), (COLLECTION_ID_0, 1)), Some(()));
+		assert_eq!(NFTCore::root_owner(COLLECTION_ID_0, 2), Ok(ALICE));
+		// Children move along with their parent
+		assert_ok!(NFTCore::send(Origin::signed(ALICE), COLLECTION_ID_0, 0, ToAccount(BOB)));
+		assert_eq!(NFTCore::root_owner(COLLECTION_ID_0, 2), Ok(BOB));
+		assert_eq!(UNQ::Pallet::<Test>::owner(COLLECTION_ID_0, 2), Some(BOB));
+		// Only the root owner may send a nested NFT, which unnests it
+		assert_noop!(
+			NFTCore::send(Origin::signed(ALICE), COLLECTION_ID_0, 2, ToAccount(ALICE)),
+			Error::<Test>::NoPermission
+		);
+		assert_ok!(NFTCore::send(Origin::signed(BOB), COLLECTION_ID_0, 2, ToAccount(CHARLIE)));
+		assert_eq!(NFTCore::parent(COLLECTION_ID_0, 2), None);
+		assert_eq!(NFTCore::children((COLLECTION_ID_0, 1), (COLLECTION_ID_0, 2)), None);
+		assert_eq!(NFTCore::root_owner(COLLECTION_ID_0, 2), Ok(CHARLIE));
+	});
+}
+
+#[test]
+fn send_nft_to_nft_prevents_cycles() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_collection();
+		basic_mint();
+		basic_mint();
+		assert_noop!(
+			NFTCore::send(Origin::signed(ALICE), COLLECTION_ID_0, 0, ToNft(COLLECTION_ID_0, 0)),
+			Error::<Test>::CannotSendToDescendantOrSelf
+		);
+		assert_ok!(NFTCore::send(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			1,
+			ToNft(COLLECTION_ID_0, 0)
+		));
+		assert_noop!(
+			NFTCore::send(Origin::signed(ALICE), COLLECTION_ID_0, 0, ToNft(COLLECTION_ID_0, 1)),
+			Error::<Test>::CannotSendToDescendantOrSelf
+		);
+		assert_noop!(
+			NFTCore::send(Origin::signed(ALICE), COLLECTION_ID_0, 0, ToNft(COLLECTION_ID_0, 42)),
+			Error::<Test>::NftUnknown
+		);
+	});
+}
+
+#[test]
+fn send_nft_to_nft_respects_max_depth() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_collection();
+		for _ in 0..5 {
+			basic_mint();
+		}
+		// Build the chain 3 -> 2 -> 1 -> 0
+		for nft_id in 1..4 {
+			assert_ok!(NFTCore::send(
+				Origin::signed(ALICE),
+				COLLECTION_ID_0,
+				nft_id,
+				ToNft(COLLECTION_ID_0, nft_id - 1)
+			));
+		}
+		assert_eq!(NFTCore::nesting_height(COLLECTION_ID_0, 0), MaxNestingDepth::get());
+		assert_noop!(
+			NFTCore::send(Origin::signed(ALICE), COLLECTION_ID_0, 4, ToNft(COLLECTION_ID_0, 3)),
+			Error::<Test>::NestingTooDeep
+		);
+		// Moving a subtree accounts for its own height
+		assert_noop!(
+			NFTCore::send(Origin::signed(ALICE), COLLECTION_ID_0, 0, ToNft(COLLECTION_ID_0, 4)),
+			Error::<Test>::NestingTooDeep
+		);
+		assert_ok!(NFTCore::send(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			1,
+			ToNft(COLLECTION_ID_0, 4)
+		));
+		assert_eq!(
+			NFTCore::ancestors(COLLECTION_ID_0, 3),
+			Ok(vec![(COLLECTION_ID_0, 2), (COLLECTION_ID_0, 1), (COLLECTION_ID_0, 4)])
+		);
+	});
+}
+
+#[test]
+fn burn_nft_burns_children() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_collection();
+		basic_mint();
+		basic_mint();
+		assert_ok!(NFTCore::send(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			1,
+			ToNft(COLLECTION_ID_0, 0)
+		));
+		assert_ok!(NFTCore::burn_nft(Origin::signed(ALICE), COLLECTION_ID_0, 0));
+		assert!(NFTCore::nfts(COLLECTION_ID_0, 1).is_none());
+		assert!(UNQ::Pallet::<Test>::owner(COLLECTION_ID_0, 1).is_none());
+		assert_eq!(NFTCore::parent(COLLECTION_ID_0, 1), None);
+		assert_eq!(Balances::reserved_balance(&ALICE), ClassDeposit::get() + metadata_deposit(11));
+	});
+}
+
+#[test]
+fn nested_nfts_cannot_be_moved_through_uniques() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_collection();
+		basic_mint();
+		basic_mint();
+		assert_ok!(NFTCore::send(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			1,
+			ToNft(COLLECTION_ID_0, 0)
+		));
+		// Neither the parent nor the child may leave the nesting tree behind
+		for nft_id in 0..2 {
+			assert_noop!(
+				UNQ::Pallet::<Test>::transfer(Origin::signed(ALICE), COLLECTION_ID_0, nft_id, BOB),
+				UNQ::Error::<Test>::Locked
+			);
+			assert_noop!(
+				UNQ::Pallet::<Test>::burn(Origin::signed(ALICE), COLLECTION_ID_0, nft_id, None),
+				UNQ::Error::<Test>::Locked
+			);
+		}
+		// Once unnested, the NFTs are free to move again
+		assert_ok!(NFTCore::send(Origin::signed(ALICE), COLLECTION_ID_0, 1, ToAccount(ALICE)));
+		assert_ok!(UNQ::Pallet::<Test>::transfer(Origin::signed(ALICE), COLLECTION_ID_0, 0, BOB));
+	});
+}
+
+#[test]
+fn nfts_cannot_be_burned_through_uniques() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_collection();
+		basic_mint();
+		// Burning through uniques would leave the nft-core data of the NFT behind
+		assert_noop!(
+			UNQ::Pallet::<Test>::burn(Origin::signed(ALICE), COLLECTION_ID_0, 0, None),
+			UNQ::Error::<Test>::Locked
+		);
+		assert_ok!(UNQ::Pallet::<Test>::transfer(Origin::signed(ALICE), COLLECTION_ID_0, 0, BOB));
+		assert_ok!(NFTCore::burn_nft(Origin::signed(BOB), COLLECTION_ID_0, 0));
+		assert_eq!(NFTCore::nfts(COLLECTION_ID_0, 0), None);
+	});
+}
+
+#[test]
+fn send_nft_to_nft_respects_max_children() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_collection();
+		for _ in 0..6 {
+			basic_mint();
+		}
+		for nft_id in 1..5 {
+			assert_ok!(NFTCore::send(
+				Origin::signed(ALICE),
+				COLLECTION_ID_0,
+				nft_id,
+				ToNft(COLLECTION_ID_0, 0)
+			));
+		}
+		assert_eq!(NFTCore::descendants(COLLECTION_ID_0, 0).len() as u32, MaxChildren::get());
+		// The whole tree counts, not only the direct children of the parent
+		assert_noop!(
+			NFTCore::send(Origin::signed(ALICE), COLLECTION_ID_0, 5, ToNft(COLLECTION_ID_0, 0)),
+			Error::<Test>::TooManyChildren
+		);
+		assert_noop!(
+			NFTCore::send(Origin::signed(ALICE), COLLECTION_ID_0, 5, ToNft(COLLECTION_ID_0, 1)),
+			Error::<Test>::TooManyChildren
+		);
+		assert_noop!(
+			NFTCore::send(Origin::signed(ALICE), COLLECTION_ID_0, 0, ToNft(COLLECTION_ID_0, 5)),
+			Error::<Test>::TooManyChildren
+		);
+		// Moving an NFT within its tree does not grow it
+		assert_ok!(NFTCore::send(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			4,
+			ToNft(COLLECTION_ID_0, 1)
+		));
+	});
+}
+
+#[test]
+fn burn_nft_checks_nested_nfts() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_collection();
+		basic_mint();
+		assert_ok!(NFTCore::send(Origin::signed(ALICE), COLLECTION_ID_0, 0, ToAccount(BOB)));
+		assert_ok!(NFTCore::create_collection(
+			Origin::signed(BOB),
+			stv("metadata"),
+			None,
+			stv("SYM"),
+			None
+		));
+		assert_ok!(NFTCore::mint_nft(
+			Origin::signed(BOB),
+			COLLECTION_ID_1,
+			None,
+			Some(BOB),
+			Some(Permill::from_percent(0)),
+			None,
+			Some(stv("metadata")),
+			true
+		));
+		assert_ok!(NFTCore::send(
+			Origin::signed(BOB),
+			COLLECTION_ID_1,
+			0,
+			ToNft(COLLECTION_ID_0, 0)
+		));
+		// The admin of the parent collection cannot burn an NFT of another collection with it
+		assert_noop!(
+			NFTCore::burn_nft(Origin::signed(ALICE), COLLECTION_ID_0, 0),
+			Error::<Test>::NoPermission
+		);
+		// Locked NFTs cannot be burned along with their parent
+		assert_ok!(<NFTCore as NftLock<_, _>>::lock(COLLECTION_ID_1, 0));
+		assert_noop!(
+			NFTCore::burn_nft(Origin::signed(BOB), COLLECTION_ID_0, 0),
+			Error::<Test>::NftLocked
+		);
+		<NFTCore as NftLock<_, _>>::unlock(COLLECTION_ID_1, 0);
+		assert_ok!(NFTCore::burn_nft(Origin::signed(BOB), COLLECTION_ID_0, 0));
+		assert_eq!(NFTCore::nfts(COLLECTION_ID_1, 0), None);
+	});
+}
+
+#[test]
+fn locks_cover_nested_nfts() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_collection();
+		basic_mint();
+		basic_mint();
+		assert_ok!(NFTCore::send(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			1,
+			ToNft(COLLECTION_ID_0, 0)
+		));
+		assert_ok!(<NFTCore as NftLock<_, _>>::lock(COLLECTION_ID_0, 0));
+		assert!(<NFTCore as NftLock<_, _>>::is_locked(COLLECTION_ID_0, 1));
+		// NFTs nested under a locked NFT can neither be taken out nor burned
+		assert_noop!(
+			NFTCore::send(Origin::signed(ALICE), COLLECTION_ID_0, 1, ToAccount(BOB)),
+			Error::<Test>::NftLocked
+		);
+		assert_noop!(
+			NFTCore::burn_nft(Origin::signed(ALICE), COLLECTION_ID_0, 1),
+			Error::<Test>::NftLocked
+		);
+		assert_noop!(
+			<NFTCore as NftLock<_, _>>::lock(COLLECTION_ID_0, 1),
+			Error::<Test>::NftLocked
+		);
+		// Nor can an NFT be locked while an NFT nested under it is
+		<NFTCore as NftLock<_, _>>::unlock(COLLECTION_ID_0, 0);
+		assert_ok!(<NFTCore as NftLock<_, _>>::lock(COLLECTION_ID_0, 1));
+		assert_noop!(
+			<NFTCore as NftLock<_, _>>::lock(COLLECTION_ID_0, 0),
+			Error::<Test>::NftLocked
+		);
+		assert_noop!(
+			NFTCore::send(Origin::signed(ALICE), COLLECTION_ID_0, 0, ToAccount(BOB)),
+			Error::<Test>::NftLocked
+		);
+	});
+}
+
+#[test]
+fn change_issuer_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_collection();
+		assert_noop!(
+			NFTCore::change_issuer(Origin::signed(BOB), COLLECTION_ID_0, BOB),
+			Error::<Test>::NoPermission
+		);
+		// An issuer delegated by the owner cannot hand the collection over
+		assert_ok!(UNQ::Pallet::<Test>::set_team(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			CHARLIE,
+			ALICE,
+			ALICE
+		));
+		assert_noop!(
+			NFTCore::change_issuer(Origin::signed(CHARLIE), COLLECTION_ID_0, CHARLIE),
+			Error::<Test>::NoPermission
+		);
+		assert_ok!(NFTCore::change_issuer(Origin::signed(ALICE), COLLECTION_ID_0, BOB));
+		assert_eq!(NFTCore::pending_issuer(COLLECTION_ID_0), Some(BOB));
+		// Nothing changes until the offer is accepted
+		assert_eq!(NFTCore::collection_issuer(COLLECTION_ID_0), Ok(CHARLIE));
+		assert_noop!(
+			NFTCore::accept_issuer(Origin::signed(CHARLIE), COLLECTION_ID_0),
+			Error::<Test>::NoPermission
+		);
+		assert_ok!(NFTCore::accept_issuer(Origin::signed(BOB), COLLECTION_ID_0));
+		assert_eq!(NFTCore::collection_issuer(COLLECTION_ID_0), Ok(BOB));
+		assert_eq!(NFTCore::pending_issuer(COLLECTION_ID_0), None);
+		assert_eq!(
+			UNQ::Pallet::<Test>::class_details(COLLECTION_ID_0).map(|details| details.owner),
+			Some(BOB)
+		);
+		// The metadata deposit stays with the account which paid it
+		assert_eq!(Balances::reserved_balance(&ALICE), metadata_deposit(11));
+		assert_eq!(Balances::reserved_balance(&BOB), ClassDeposit::get());
+		assert_noop!(
+			NFTCore::accept_issuer(Origin::signed(BOB), COLLECTION_ID_0),
+			Error::<Test>::NoPendingIssuer
+		);
+	});
+}
+
+#[test]
+fn cancel_issuer_change_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_collection();
+		assert_ok!(NFTCore::change_issuer(Origin::signed(ALICE), COLLECTION_ID_0, BOB));
+		assert_noop!(
+			NFTCore::cancel_issuer_change(Origin::signed(CHARLIE), COLLECTION_ID_0),
+			Error::<Test>::NoPermission
+		);
+		assert_ok!(NFTCore::cancel_issuer_change(Origin::signed(BOB), COLLECTION_ID_0));
+		assert_noop!(
+			NFTCore::accept_issuer(Origin::signed(BOB), COLLECTION_ID_0),
+			Error::<Test>::NoPendingIssuer
+		);
+		assert_ok!(NFTCore::change_issuer(Origin::signed(ALICE), COLLECTION_ID_0, BOB));
+		assert_ok!(NFTCore::cancel_issuer_change(Origin::signed(ALICE), COLLECTION_ID_0));
+		assert_eq!(NFTCore::collection_issuer(COLLECTION_ID_0), Ok(ALICE));
+	});
+}
+
+#[test]
+fn set_property_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_collection();
+		basic_mint();
+		assert_ok!(NFTCore::send(Origin::signed(ALICE), COLLECTION_ID_0, 0, ToAccount(BOB)));
+		let reserved_alice = Balances::reserved_balance(ALICE);
+		// Only the issuer may set collection properties
+		assert_noop!(
+			NFTCore::set_property(
+				Origin::signed(BOB),
+				COLLECTION_ID_0,
+				None,
+				stbk("name"),
+				stb("game")
+			),
+			Error::<Test>::NoPermission
+		);
+		assert_ok!(NFTCore::set_property(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			None,
+			stbk("name"),
+			stb("game")
+		));
+		assert_eq!(
+			NFTCore::properties((COLLECTION_ID_0, None, stbk("name"))).unwrap().0,
+			stb("game")
+		);
+		// Deposit is the attribute base plus the per byte deposit for key and value
+		assert_eq!(Balances::reserved_balance(ALICE), reserved_alice + 100 + 10 * 8);
+		// Both the owner and the issuer may set nft properties
+		assert_ok!(NFTCore::set_property(
+			Origin::signed(BOB),
+			COLLECTION_ID_0,
+			Some(0),
+			stbk("hp"),
+			stb("10")
+		));
+		assert_eq!(Balances::reserved_balance(BOB), 100 + 10 * 4);
+		assert_eq!(
+			NFTCore::properties((COLLECTION_ID_0, Some(0), stbk("hp"))).unwrap().1,
+			PropertySetter::Owner
+		);
+		assert_ok!(NFTCore::set_property(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			Some(0),
+			stbk("hp"),
+			stb("100")
+		));
+		assert_eq!(
+			NFTCore::properties((COLLECTION_ID_0, Some(0), stbk("hp"))).unwrap().0,
+			stb("100")
+		);
+		// The issuer is recorded as the setter in place of the owner
+		assert_eq!(
+			NFTCore::properties((COLLECTION_ID_0, Some(0), stbk("hp"))).unwrap().1,
+			PropertySetter::Issuer
+		);
+		// Replacing a value refunds the previous deposit
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(Balances::reserved_balance(ALICE), reserved_alice + 100 + 10 * 8 + 100 + 10 * 5);
+		assert_noop!(
+			NFTCore::set_property(
+				Origin::signed(CHARLIE),
+				COLLECTION_ID_0,
+				Some(0),
+				stbk("hp"),
+				stb("1")
+			),
+			Error::<Test>::NoPermission
+		);
+		assert_noop!(
+			NFTCore::set_property(
+				Origin::signed(ALICE),
+				COLLECTION_ID_0,
+				Some(1),
+				stbk("hp"),
+				stb("1")
+			),
+			Error::<Test>::NftUnknown
+		);
+		// Properties set through the protocol origin are free
+		assert_ok!(NFTCore::set_property(
+			Origin::root(),
+			COLLECTION_ID_0,
+			Some(0),
+			stbk("xp"),
+			stb("0")
+		));
+		assert_eq!(
+			NFTCore::properties((COLLECTION_ID_0, Some(0), stbk("xp"))),
+			Some((stb("0"), PropertySetter::Protocol, None))
+		);
+	});
+}
+
+#[test]
+fn remove_property_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_collection();
+		basic_mint();
+		assert_ok!(NFTCore::send(Origin::signed(ALICE), COLLECTION_ID_0, 0, ToAccount(BOB)));
+		assert_ok!(NFTCore::set_property(
+			Origin::signed(BOB),
+			COLLECTION_ID_0,
+			Some(0),
+			stbk("hp"),
+			stb("10")
+		));
+		assert_noop!(
+			NFTCore::remove_property(Origin::signed(CHARLIE), COLLECTION_ID_0, Some(0), stbk("hp")),
+			Error::<Test>::NoPermission
+		);
+		assert_noop!(
+			NFTCore::remove_property(Origin::signed(BOB), COLLECTION_ID_0, Some(0), stbk("xp")),
+			Error::<Test>::PropertyUnknown
+		);
+		assert_ok!(NFTCore::remove_property(
+			Origin::signed(BOB),
+			COLLECTION_ID_0,
+			Some(0),
+			stbk("hp")
+		));
+		assert_eq!(NFTCore::properties((COLLECTION_ID_0, Some(0), stbk("hp"))), None);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		// Burning an NFT removes its properties and refunds their deposits
+		assert_ok!(NFTCore::set_property(
+			Origin::signed(BOB),
+			COLLECTION_ID_0,
+			Some(0),
+			stbk("hp"),
+			stb("10")
+		));
+		assert_ok!(NFTCore::burn_nft(Origin::signed(BOB), COLLECTION_ID_0, 0));
+		assert_eq!(NFTCore::properties((COLLECTION_ID_0, Some(0), stbk("hp"))), None);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+	});
+}
+
+#[test]
+fn mint_beyond_collection_max_fails() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NFTCore::create_collection(
+			Origin::signed(ALICE),
+			stv("metadata"),
+			Some(2),
+			stv("SYM"),
+			None
+		));
+		assert_eq!(NFTCore::collections(COLLECTION_ID_0).unwrap().max, Some(2));
+		basic_mint();
+		basic_mint();
+		assert_noop!(
+			NFTCore::mint_nft(
+				Origin::signed(ALICE),
+				COLLECTION_ID_0,
+				None,
+				Some(ALICE),
+				Some(Permill::from_percent(0)),
+				None,
+				Some(stv("metadata")),
+				true
+			),
+			Error::<Test>::CollectionFullOrLocked
+		);
+	});
+}
+
+#[test]
+fn lock_collection_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_collection();
+		basic_mint();
+		assert_noop!(
+			NFTCore::lock_collection(Origin::signed(BOB), COLLECTION_ID_0),
+			Error::<Test>::NoPermission
+		);
+		assert_ok!(NFTCore::lock_collection(Origin::signed(ALICE), COLLECTION_ID_0));
+		assert_eq!(NFTCore::collections(COLLECTION_ID_0).unwrap().max, Some(1));
+		assert_noop!(
+			NFTCore::mint_nft(
+				Origin::signed(ALICE),
+				COLLECTION_ID_0,
+				None,
+				Some(ALICE),
+				Some(Permill::from_percent(0)),
+				None,
+				Some(stv("metadata")),
+				true
+			),
+			Error::<Test>::CollectionFullOrLocked
+		);
+	});
+}
+
+#[test]
+fn add_resource_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_collection();
+		basic_mint();
+		assert_noop!(
+			NFTCore::add_resource(Origin::signed(BOB), COLLECTION_ID_0, 0, 0, basic_resource()),
+			Error::<Test>::NoPermission
+		);
+		assert_noop!(
+			NFTCore::add_resource(Origin::signed(ALICE), COLLECTION_ID_0, 1, 0, basic_resource()),
+			Error::<Test>::NftUnknown
+		);
+		assert_ok!(NFTCore::add_resource(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			0,
+			0,
+			basic_resource()
+		));
+		assert_eq!(
+			NFTCore::resources((COLLECTION_ID_0, 0, 0)).map(|(resource, _)| resource),
+			Some(basic_resource())
+		);
+		assert_noop!(
+			NFTCore::add_resource(Origin::signed(ALICE), COLLECTION_ID_0, 0, 0, basic_resource()),
+			Error::<Test>::ResourceAlreadyExists
+		);
+		// Resources are keyed by collection, so the same NFT id in another collection is separate
+		assert_ok!(NFTCore::create_collection(
+			Origin::signed(ALICE),
+			stv("metadata"),
+			None,
+			stv("SYM"),
+			None
+		));
+		assert_ok!(NFTCore::mint_nft(
+			Origin::signed(ALICE),
+			COLLECTION_ID_1,
+			None,
+			Some(ALICE),
+			Some(Permill::from_percent(0)),
+			None,
+			Some(stv("metadata")),
+			true
+		));
+		assert_eq!(NFTCore::resources((COLLECTION_ID_1, 0, 0)), None);
+		// Burning an NFT removes its resources
+		assert_ok!(NFTCore::burn_nft(Origin::signed(ALICE), COLLECTION_ID_0, 0));
+		assert_eq!(NFTCore::resources((COLLECTION_ID_0, 0, 0)), None);
+	});
+}
+
+#[test]
+fn accept_and_reject_resource_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_collection();
+		basic_mint();
+		assert_ok!(NFTCore::send(Origin::signed(ALICE), COLLECTION_ID_0, 0, ToAccount(BOB)));
+		assert_noop!(
+			NFTCore::add_resource(Origin::signed(CHARLIE), COLLECTION_ID_0, 0, 0, basic_resource()),
+			Error::<Test>::NoPermission
+		);
+		// Resources added by the issuer to an NFT of BOB are pending
+		assert_ok!(NFTCore::add_resource(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			0,
+			0,
+			basic_resource()
+		));
+		assert_ok!(NFTCore::add_resource(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			0,
+			1,
+			basic_resource()
+		));
+		assert!(NFTCore::resources((COLLECTION_ID_0, 0, 0)).unwrap().0.pending);
+		// Resources added by the owner are accepted right away
+		assert_ok!(NFTCore::add_resource(
+			Origin::signed(BOB),
+			COLLECTION_ID_0,
+			0,
+			2,
+			basic_resource()
+		));
+		assert!(!NFTCore::resources((COLLECTION_ID_0, 0, 2)).unwrap().0.pending);
+
+		assert_noop!(
+			NFTCore::accept(Origin::signed(ALICE), COLLECTION_ID_0, 0, 0),
+			Error::<Test>::NoPermission
+		);
+		assert_noop!(
+			NFTCore::accept(Origin::signed(BOB), COLLECTION_ID_0, 0, 2),
+			Error::<Test>::ResourceNotPending
+		);
+		assert_noop!(
+			NFTCore::accept(Origin::signed(BOB), COLLECTION_ID_0, 0, 3),
+			Error::<Test>::ResourceUnknown
+		);
+		assert_ok!(NFTCore::accept(Origin::signed(BOB), COLLECTION_ID_0, 0, 0));
+		assert!(!NFTCore::resources((COLLECTION_ID_0, 0, 0)).unwrap().0.pending);
+
+		assert_noop!(
+			NFTCore::reject_resource(Origin::signed(ALICE), COLLECTION_ID_0, 0, 1),
+			Error::<Test>::NoPermission
+		);
+		assert_noop!(
+			NFTCore::reject_resource(Origin::signed(BOB), COLLECTION_ID_0, 0, 0),
+			Error::<Test>::ResourceNotPending
+		);
+		assert_ok!(NFTCore::reject_resource(Origin::signed(BOB), COLLECTION_ID_0, 0, 1));
+		assert_eq!(NFTCore::resources((COLLECTION_ID_0, 0, 1)), None);
+	});
+}
+
+#[test]
+fn set_priority_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_collection();
+		basic_mint();
+		assert_ok!(NFTCore::send(Origin::signed(ALICE), COLLECTION_ID_0, 0, ToAccount(BOB)));
+		assert_ok!(NFTCore::add_resource(
+			Origin::signed(BOB),
+			COLLECTION_ID_0,
+			0,
+			0,
+			basic_resource()
+		));
+		assert_ok!(NFTCore::add_resource(
+			Origin::signed(BOB),
+			COLLECTION_ID_0,
+			0,
+			1,
+			basic_resource()
+		));
+		assert_ok!(NFTCore::add_resource(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			0,
+			2,
+			basic_resource()
+		));
+		let priorities: BoundedVec<u32, MaxPriorities> = vec![1, 0].try_into().unwrap();
+		assert_noop!(
+			NFTCore::set_priority(Origin::signed(ALICE), COLLECTION_ID_0, 0, priorities.clone()),
+			Error::<Test>::NoPermission
+		);
+		assert_noop!(
+			NFTCore::set_priority(
+				Origin::signed(BOB),
+				COLLECTION_ID_0,
+				0,
+				vec![1, 3].try_into().unwrap()
+			),
+			Error::<Test>::ResourceUnknown
+		);
+		assert_noop!(
+			NFTCore::set_priority(
+				Origin::signed(BOB),
+				COLLECTION_ID_0,
+				0,
+				vec![2, 0].try_into().unwrap()
+			),
+			Error::<Test>::ResourcePending
+		);
+		assert_ok!(NFTCore::set_priority(
+			Origin::signed(BOB),
+			COLLECTION_ID_0,
+			0,
+			priorities.clone()
+		));
+		assert_eq!(NFTCore::priorities(COLLECTION_ID_0, 0), Some(priorities));
+		// Removing a resource drops it from the priority list
+		assert_noop!(
+			NFTCore::remove_resource(Origin::signed(ALICE), COLLECTION_ID_0, 0, 1),
+			Error::<Test>::NoPermission
+		);
+		assert_ok!(NFTCore::remove_resource(Origin::signed(BOB), COLLECTION_ID_0, 0, 1));
+		assert_eq!(NFTCore::resources((COLLECTION_ID_0, 0, 1)), None);
+		assert_eq!(NFTCore::priorities(COLLECTION_ID_0, 0).unwrap().into_inner(), vec![0]);
+		assert_ok!(NFTCore::remove_resource(Origin::signed(BOB), COLLECTION_ID_0, 0, 0));
+		assert_eq!(NFTCore::priorities(COLLECTION_ID_0, 0), None);
+		assert_noop!(
+			NFTCore::remove_resource(Origin::signed(BOB), COLLECTION_ID_0, 0, 0),
+			Error::<Test>::ResourceUnknown
+		);
+	});
+}
+
+#[test]
+fn mint_nft_checks_royalty() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_collection();
+		assert_noop!(
+			NFTCore::mint_nft(
+				Origin::signed(ALICE),
+				COLLECTION_ID_0,
+				None,
+				Some(ALICE),
+				Some(Permill::one()),
+				None,
+				Some(stv("metadata")),
+				true
+			),
+			Error::<Test>::NotInRange
+		);
+		assert_noop!(
+			NFTCore::mint_nft(
+				Origin::signed(ALICE),
+				COLLECTION_ID_0,
+				None,
+				Some(ALICE),
+				Some(Permill::from_percent(10)),
+				Some(
+					vec![(BOB, Permill::from_percent(60)), (CHARLIE, Permill::from_percent(50))]
+						.try_into()
+						.unwrap()
+				),
+				Some(stv("metadata")),
+				true
+			),
+			Error::<Test>::RoyaltySharesTooHigh
+		);
+	});
+}
+
+#[test]
+fn pay_royalty_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_collection();
+		assert_ok!(NFTCore::mint_nft(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			None,
+			Some(ALICE),
+			Some(Permill::from_percent(10)),
+			Some(vec![(BOB, Permill::from_percent(50))].try_into().unwrap()),
+			Some(stv("metadata")),
+			true
+		));
+		let free_alice = Balances::free_balance(ALICE);
+		let free_bob = Balances::free_balance(BOB);
+		let free_charlie = Balances::free_balance(CHARLIE);
+		assert_eq!(NFTCore::pay_royalty(&CHARLIE, COLLECTION_ID_0, 0, 1_000), Ok(900));
+		// The recipients get their share of the royalty, the author gets the rest
+		assert_eq!(Balances::free_balance(BOB), free_bob + 50);
+		assert_eq!(Balances::free_balance(ALICE), free_alice + 50);
+		assert_eq!(Balances::free_balance(CHARLIE), free_charlie - 100);
+		assert_noop!(
+			NFTCore::pay_royalty(&CHARLIE, COLLECTION_ID_0, 1, 1_000),
+			Error::<Test>::NftUnknown
+		);
+	});
+}
+
+#[test]
+fn pay_royalty_skips_shares_below_existential_deposit() {
+	ExtBuilder::default().build().execute_with(|| {
+		ExistentialDeposit::set(100);
+		basic_collection();
+		assert_ok!(NFTCore::mint_nft(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			None,
+			Some(ALICE),
+			Some(Permill::from_percent(10)),
+			Some(vec![(DAVE, Permill::from_percent(50))].try_into().unwrap()),
+			Some(stv("metadata")),
+			true
+		));
+		// The share of DAVE cannot create the account of DAVE, so the author gets it instead
+		let free_alice = Balances::free_balance(ALICE);
+		assert_eq!(NFTCore::pay_royalty(&CHARLIE, COLLECTION_ID_0, 0, 1_000), Ok(900));
+		assert_eq!(Balances::free_balance(DAVE), 0);
+		assert_eq!(Balances::free_balance(ALICE), free_alice + 100);
+
+		assert_ok!(NFTCore::mint_nft(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			None,
+			Some(DAVE),
+			Some(Permill::from_percent(10)),
+			None,
+			Some(stv("metadata")),
+			true
+		));
+		// Neither can the royalty of an author without an account, so it is left for the seller
+		let free_charlie = Balances::free_balance(CHARLIE);
+		assert_eq!(NFTCore::pay_royalty(&CHARLIE, COLLECTION_ID_0, 1, 500), Ok(500));
+		assert_eq!(Balances::free_balance(CHARLIE), free_charlie);
+		assert_eq!(NFTCore::pay_royalty(&CHARLIE, COLLECTION_ID_0, 1, 1_000), Ok(900));
+		assert_eq!(Balances::free_balance(DAVE), 100);
+	});
+}
+
+/// Creates a base of ALICE with a fixed part 0, a slot part 1 for collection 0 and a slot part 2
+/// which nothing may be equipped into
+fn basic_base() {
+	let parts = vec![
+		PartType::FixedPart(FixedPart { id: 0, z: 0, src: stbs("ipfs://body") }),
+		PartType::SlotPart(SlotPart {
+			id: 1,
+			equippable: EquippableList::Custom(vec![COLLECTION_ID_0].try_into().unwrap()),
+			z: 1,
+			src: stbs("ipfs://hand"),
+		}),
+		PartType::SlotPart(SlotPart {
+			id: 2,
+			equippable: EquippableList::Empty,
+			z: 2,
+			src: stbs("ipfs://head"),
+		}),
+	];
+	assert_ok!(NFTCore::create_base(
+		Origin::signed(ALICE),
+		stv("svg"),
+		stv("BASE"),
+		parts.try_into().unwrap(),
+		None
+	));
+}
+
+/// Returns a resource of the given base, either composed from `parts` or fitting into `slot`
+fn base_resource(parts: Option<Vec<u32>>, slot: Option<u32>) -> ResourceInfoOf<Test> {
+	ResourceInfo {
+		base: Some(0),
+		parts: parts.map(|parts| parts.try_into().unwrap()),
+		slot,
+		..basic_resource()
+	}
+}
+
+#[test]
+fn create_base_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_base();
+		let base = NFTCore::bases(0).unwrap();
+		assert_eq!(base.issuer, ALICE);
+		let deposit = base.deposit.unwrap();
+		assert_eq!(deposit.account, ALICE);
+		assert!(deposit.amount > metadata_deposit(7));
+		assert_eq!(Balances::reserved_balance(&ALICE), deposit.amount);
+		assert_eq!(NFTCore::next_base_id(), 1);
+		assert!(matches!(NFTCore::parts(0, 1), Some(PartType::SlotPart(_))));
+		let duplicate_parts = vec![
+			PartType::FixedPart(FixedPart { id: 0, z: 0, src: stbs("ipfs://body") }),
+			PartType::FixedPart(FixedPart { id: 0, z: 1, src: stbs("ipfs://body") }),
+		];
+		assert_noop!(
+			NFTCore::create_base(
+				Origin::signed(ALICE),
+				stv("svg"),
+				stv("BASE"),
+				duplicate_parts.try_into().unwrap(),
+				None
+			),
+			Error::<Test>::PartAlreadyExists
+		);
+
+		// Only the protocol origin may create a base for another account, without a deposit
+		assert_noop!(
+			NFTCore::create_base(
+				Origin::signed(ALICE),
+				stv("svg"),
+				stv("BASE"),
+				Default::default(),
+				Some(BOB)
+			),
+			Error::<Test>::NoPermission
+		);
+		assert_noop!(
+			NFTCore::create_base(Origin::root(), stv("svg"), stv("BASE"), Default::default(), None),
+			Error::<Test>::IssuerNotSet
+		);
+		assert_ok!(NFTCore::create_base(
+			Origin::root(),
+			stv("svg"),
+			stv("BASE"),
+			Default::default(),
+			Some(BOB)
+		));
+		assert_eq!(NFTCore::bases(1).unwrap().issuer, BOB);
+		assert_eq!(Balances::reserved_balance(&BOB), 0);
+	});
+}
+
+#[test]
+fn equip_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_collection();
+		basic_base();
+		// NFT 0 is a character composed from the base, NFT 1 a sword fitting into slot 1
+		basic_mint();
+		basic_mint();
+		assert_ok!(NFTCore::add_resource(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			0,
+			0,
+			base_resource(Some(vec![0, 1, 2]), None)
+		));
+		assert_ok!(NFTCore::add_resource(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			1,
+			0,
+			base_resource(None, Some(1))
+		));
+		assert_noop!(
+			NFTCore::equip(
+				Origin::signed(ALICE),
+				(COLLECTION_ID_0, 1),
+				(COLLECTION_ID_0, 0),
+				0,
+				0,
+				1
+			),
+			Error::<Test>::MustBeDirectParent
+		);
+		assert_ok!(NFTCore::send(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			1,
+			ToNft(COLLECTION_ID_0, 0)
+		));
+		assert_noop!(
+			NFTCore::equip(
+				Origin::signed(BOB),
+				(COLLECTION_ID_0, 1),
+				(COLLECTION_ID_0, 0),
+				0,
+				0,
+				1
+			),
+			Error::<Test>::NoPermission
+		);
+		assert_noop!(
+			NFTCore::equip(
+				Origin::signed(ALICE),
+				(COLLECTION_ID_0, 1),
+				(COLLECTION_ID_0, 0),
+				0,
+				0,
+				2
+			),
+			Error::<Test>::ItemNotEquippable
+		);
+		assert_ok!(NFTCore::equip(
+			Origin::signed(ALICE),
+			(COLLECTION_ID_0, 1),
+			(COLLECTION_ID_0, 0),
+			0,
+			0,
+			1
+		));
+		assert_eq!(NFTCore::equippings((COLLECTION_ID_0, 0, 0, 1)), Some((COLLECTION_ID_0, 1)));
+		assert_eq!(NFTCore::equipped(COLLECTION_ID_0, 1), Some((COLLECTION_ID_0, 0, 0, 1)));
+		assert_noop!(
+			NFTCore::equip(
+				Origin::signed(ALICE),
+				(COLLECTION_ID_0, 1),
+				(COLLECTION_ID_0, 0),
+				0,
+				0,
+				1
+			),
+			Error::<Test>::ItemAlreadyEquipped
+		);
+		// Equipped items cannot be sent away, but their parent can
+		assert_noop!(
+			NFTCore::send(Origin::signed(ALICE), COLLECTION_ID_0, 1, ToAccount(BOB)),
+			Error::<Test>::CannotSendEquippedItem
+		);
+		assert_ok!(NFTCore::send(Origin::signed(ALICE), COLLECTION_ID_0, 0, ToAccount(BOB)));
+		assert_noop!(
+			NFTCore::unequip(Origin::signed(ALICE), (COLLECTION_ID_0, 1)),
+			Error::<Test>::NoPermission
+		);
+		assert_ok!(NFTCore::unequip(Origin::signed(BOB), (COLLECTION_ID_0, 1)));
+		assert_eq!(NFTCore::equippings((COLLECTION_ID_0, 0, 0, 1)), None);
+		assert_eq!(NFTCore::equipped(COLLECTION_ID_0, 1), None);
+		assert_noop!(
+			NFTCore::unequip(Origin::signed(BOB), (COLLECTION_ID_0, 1)),
+			Error::<Test>::NotEquipped
+		);
+		assert_ok!(NFTCore::send(Origin::signed(BOB), COLLECTION_ID_0, 1, ToAccount(BOB)));
+	});
+}
+
+#[test]
+fn remove_resource_unequips_unfitting_items() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_collection();
+		basic_base();
+		basic_mint();
+		basic_mint();
+		assert_ok!(NFTCore::add_resource(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			0,
+			0,
+			base_resource(Some(vec![0, 1, 2]), None)
+		));
+		for resource_id in 0..2 {
+			assert_ok!(NFTCore::add_resource(
+				Origin::signed(ALICE),
+				COLLECTION_ID_0,
+				1,
+				resource_id,
+				base_resource(None, Some(1))
+			));
+		}
+		assert_ok!(NFTCore::send(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			1,
+			ToNft(COLLECTION_ID_0, 0)
+		));
+		assert_ok!(NFTCore::equip(
+			Origin::signed(ALICE),
+			(COLLECTION_ID_0, 1),
+			(COLLECTION_ID_0, 0),
+			0,
+			0,
+			1
+		));
+		// The item stays equipped as long as one of its resources fits into the slot
+		assert_ok!(NFTCore::remove_resource(Origin::signed(ALICE), COLLECTION_ID_0, 1, 0));
+		assert_eq!(NFTCore::equipped(COLLECTION_ID_0, 1), Some((COLLECTION_ID_0, 0, 0, 1)));
+		assert_ok!(NFTCore::remove_resource(Origin::signed(ALICE), COLLECTION_ID_0, 1, 1));
+		assert_eq!(NFTCore::equippings((COLLECTION_ID_0, 0, 0, 1)), None);
+		assert_eq!(NFTCore::equipped(COLLECTION_ID_0, 1), None);
+
+		// Removing the resource of the equipper which includes the slot unequips the item too
+		assert_ok!(NFTCore::add_resource(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			1,
+			0,
+			base_resource(None, Some(1))
+		));
+		assert_ok!(NFTCore::equip(
+			Origin::signed(ALICE),
+			(COLLECTION_ID_0, 1),
+			(COLLECTION_ID_0, 0),
+			0,
+			0,
+			1
+		));
+		assert_ok!(NFTCore::remove_resource(Origin::signed(ALICE), COLLECTION_ID_0, 0, 0));
+		assert_eq!(NFTCore::equippings((COLLECTION_ID_0, 0, 0, 1)), None);
+		assert_eq!(NFTCore::equipped(COLLECTION_ID_0, 1), None);
+		assert_ok!(NFTCore::send(Origin::signed(ALICE), COLLECTION_ID_0, 1, ToAccount(BOB)));
+	});
+}
+
+#[test]
+fn equip_respects_parts() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_collection();
+		basic_base();
+		basic_mint();
+		basic_mint();
+		assert_ok!(NFTCore::add_resource(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			0,
+			0,
+			base_resource(Some(vec![0, 2]), None)
+		));
+		assert_ok!(NFTCore::add_resource(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			1,
+			0,
+			base_resource(None, Some(0))
+		));
+		assert_ok!(NFTCore::add_resource(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			1,
+			1,
+			base_resource(None, Some(1))
+		));
+		assert_ok!(NFTCore::add_resource(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			1,
+			2,
+			base_resource(None, Some(2))
+		));
+		assert_ok!(NFTCore::send(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			1,
+			ToNft(COLLECTION_ID_0, 0)
+		));
+		// The resource of NFT 0 does not include slot 1
+		assert_noop!(
+			NFTCore::equip(
+				Origin::signed(ALICE),
+				(COLLECTION_ID_0, 1),
+				(COLLECTION_ID_0, 0),
+				1,
+				0,
+				1
+			),
+			Error::<Test>::EquipperHasNoSlot
+		);
+		assert_noop!(
+			NFTCore::equip(
+				Origin::signed(ALICE),
+				(COLLECTION_ID_0, 1),
+				(COLLECTION_ID_0, 0),
+				0,
+				0,
+				0
+			),
+			Error::<Test>::PartNotSlot
+		);
+		assert_noop!(
+			NFTCore::equip(
+				Origin::signed(ALICE),
+				(COLLECTION_ID_0, 1),
+				(COLLECTION_ID_0, 0),
+				2,
+				0,
+				2
+			),
+			Error::<Test>::CollectionNotEquippable
+		);
+		// Burning the parent removes its equippings along with the nested item
+		assert_ok!(NFTCore::add_resource(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			0,
+			1,
+			base_resource(Some(vec![1]), None)
+		));
+		assert_ok!(NFTCore::equip(
+			Origin::signed(ALICE),
+			(COLLECTION_ID_0, 1),
+			(COLLECTION_ID_0, 0),
+			1,
+			0,
+			1
+		));
+		assert_ok!(NFTCore::burn_nft(Origin::signed(ALICE), COLLECTION_ID_0, 0));
+		assert_eq!(NFTCore::equippings((COLLECTION_ID_0, 0, 0, 1)), None);
+		assert_eq!(NFTCore::equipped(COLLECTION_ID_0, 1), None);
+	});
+}
+
+#[test]
+fn add_theme_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_base();
+		let property = |key: &str, value: &str, inherit: bool| ThemeProperty {
+			key: stbk(key),
+			value: stb(value),
+			inherit,
+		};
+		let properties: BoundedVec<_, MaxPropertiesPerTheme> =
+			vec![property("primary_color", "#ff00ff", true), property("font", "serif", false)]
+				.try_into()
+				.unwrap();
+		assert_noop!(
+			NFTCore::add_theme(Origin::signed(BOB), 0, stbs("default"), properties.clone()),
+			Error::<Test>::NoPermission
+		);
+		assert_noop!(
+			NFTCore::add_theme(Origin::signed(ALICE), 1, stbs("default"), properties.clone()),
+			Error::<Test>::BaseUnknown
+		);
+		assert_noop!(
+			NFTCore::add_theme(
+				Origin::signed(ALICE),
+				0,
+				stbs("default"),
+				vec![property("font", "serif", false), property("font", "mono", true)]
+					.try_into()
+					.unwrap()
+			),
+			Error::<Test>::DuplicateThemeProperty
+		);
+		assert_ok!(NFTCore::add_theme(
+			Origin::signed(ALICE),
+			0,
+			stbs("default"),
+			properties.clone()
+		));
+		let base_deposit = NFTCore::bases(0).unwrap().deposit.unwrap().amount;
+		let deposit = metadata_deposit(7 + properties.encoded_size() as u64);
+		assert_eq!(NFTCore::themes(0, stbs("default")).map(|(theme, _)| theme), Some(properties));
+		assert_eq!(Balances::reserved_balance(&ALICE), base_deposit + deposit);
+		// Adding a theme of the same name restyles the base, refunding the replaced deposit
+		let night: BoundedVec<_, MaxPropertiesPerTheme> =
+			vec![property("primary_color", "#000000", true)].try_into().unwrap();
+		assert_ok!(NFTCore::add_theme(Origin::signed(ALICE), 0, stbs("default"), night.clone()));
+		let deposit = metadata_deposit(7 + night.encoded_size() as u64);
+		assert_eq!(NFTCore::themes(0, stbs("default")).map(|(theme, _)| theme), Some(night));
+		assert_eq!(Balances::reserved_balance(&ALICE), base_deposit + deposit);
+	});
+}
+
+#[test]
+fn soulbound_nft_cannot_be_transferred() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_collection();
+		basic_mint();
+		assert_ok!(NFTCore::mint_nft(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			None,
+			Some(ALICE),
+			Some(Permill::from_percent(0)),
+			None,
+			Some(stv("badge")),
+			false
+		));
+		assert!(!NFTCore::nfts(COLLECTION_ID_0, 1).unwrap().transferable);
+		assert_noop!(
+			NFTCore::send(Origin::signed(ALICE), COLLECTION_ID_0, 1, ToAccount(BOB)),
+			Error::<Test>::NonTransferable
+		);
+		assert_noop!(
+			NFTCore::send(Origin::signed(ALICE), COLLECTION_ID_0, 1, ToNft(COLLECTION_ID_0, 0)),
+			Error::<Test>::NonTransferable
+		);
+		assert_noop!(
+			UNQ::Pallet::<Test>::transfer(Origin::signed(ALICE), COLLECTION_ID_0, 1, BOB),
+			UNQ::Error::<Test>::Locked
+		);
+		// Transferable NFTs are not affected
+		assert_ok!(UNQ::Pallet::<Test>::transfer(Origin::signed(ALICE), COLLECTION_ID_0, 0, BOB));
+		// Soulbound NFTs can still be burned by their owner
+		assert_ok!(NFTCore::burn_nft(Origin::signed(ALICE), COLLECTION_ID_0, 1));
+		assert_eq!(NFTCore::nfts(COLLECTION_ID_0, 1), None);
+	});
+}
+
+#[test]
+fn mint_nft_batch_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_collection();
+		let royalty = Permill::from_percent(5);
+		assert_ok!(NFTCore::mint_nft_batch(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			None,
+			vec![(BOB, royalty, stv("first")), (CHARLIE, royalty, stv("second"))]
+		));
+		assert_eq!(UNQ::Pallet::<Test>::owner(COLLECTION_ID_0, 0), Some(BOB));
+		assert_eq!(UNQ::Pallet::<Test>::owner(COLLECTION_ID_0, 1), Some(CHARLIE));
+		let nft = NFTCore::nfts(COLLECTION_ID_0, 1).unwrap();
+		assert_eq!(nft.author, ALICE);
+		assert_eq!(nft.metadata, stbs("second"));
+		assert_eq!(NFTCore::next_nft_id(COLLECTION_ID_0), 2);
+		// The protocol origin must name the author
+		assert_noop!(
+			NFTCore::mint_nft_batch(
+				Origin::root(),
+				COLLECTION_ID_0,
+				None,
+				vec![(BOB, royalty, stv("third"))]
+			),
+			Error::<Test>::AuthorNotSet
+		);
+		assert_ok!(NFTCore::mint_nft_batch(
+			Origin::root(),
+			COLLECTION_ID_0,
+			Some(CHARLIE),
+			vec![(BOB, royalty, stv("third"))]
+		));
+		assert_eq!(NFTCore::nfts(COLLECTION_ID_0, 2).unwrap().author, CHARLIE);
+		assert_noop!(
+			NFTCore::mint_nft_batch(
+				Origin::signed(ALICE),
+				COLLECTION_ID_0,
+				None,
+				vec![(BOB, royalty, stv("metadata")); MaxBatchMint::get() as usize + 1]
+			),
+			Error::<Test>::TooManyItems
+		);
+		// A single invalid item fails the whole batch
+		assert_noop!(
+			NFTCore::mint_nft_batch(
+				Origin::signed(ALICE),
+				COLLECTION_ID_0,
+				None,
+				vec![
+					(BOB, royalty, stv("metadata")),
+					(BOB, royalty, vec![0; UniquesStringLimit::get() as usize + 1])
+				]
+			),
+			Error::<Test>::TooLong
+		);
+	});
+}
+
+/// Returns the destroy witness of the collection
+fn collection_witness() -> UNQ::DestroyWitness {
+	UNQ::Pallet::<Test>::class_details(COLLECTION_ID_0).unwrap().destroy_witness()
+}
+
+#[test]
+fn burn_empty_collection_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_collection();
+		assert_noop!(
+			NFTCore::burn_collection(Origin::signed(BOB), COLLECTION_ID_0, collection_witness()),
+			Error::<Test>::NoPermission
+		);
+		basic_mint();
+		let witness = collection_witness();
+		assert_ok!(NFTCore::burn_nft(Origin::signed(ALICE), COLLECTION_ID_0, 0));
+		assert_noop!(
+			NFTCore::burn_collection(Origin::signed(ALICE), COLLECTION_ID_0, witness),
+			UNQ::Error::<Test>::BadWitness
+		);
+		assert_ok!(NFTCore::burn_collection(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			collection_witness()
+		));
+		assert_eq!(NFTCore::collections(COLLECTION_ID_0), None);
+		assert_eq!(Balances::reserved_balance(&ALICE), 0);
+		assert_noop!(
+			NFTCore::burn_collection(Origin::signed(ALICE), COLLECTION_ID_0, witness),
+			Error::<Test>::CollectionUnknown
+		);
+	});
+}
+
+#[test]
+fn burn_collection_cleans_up_on_idle() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_collection();
+		basic_mint();
+		basic_mint();
+		assert_ok!(NFTCore::send(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			1,
+			ToNft(COLLECTION_ID_0, 0)
+		));
+		assert_ok!(NFTCore::set_property(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			None,
+			stbk("name"),
+			stb("game")
+		));
+		assert_ok!(NFTCore::burn_collection(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			collection_witness()
+		));
+		assert!(NFTCore::collections(COLLECTION_ID_0).is_some());
+		assert_noop!(
+			NFTCore::burn_collection(Origin::signed(ALICE), COLLECTION_ID_0, collection_witness()),
+			Error::<Test>::CollectionBeingDestroyed
+		);
+		assert_noop!(
+			NFTCore::mint_nft(
+				Origin::signed(ALICE),
+				COLLECTION_ID_0,
+				None,
+				Some(ALICE),
+				Some(Permill::from_percent(0)),
+				None,
+				Some(stv("metadata")),
+				true
+			),
+			Error::<Test>::CollectionBeingDestroyed
+		);
+		assert_noop!(
+			NFTCore::send(Origin::signed(ALICE), COLLECTION_ID_0, 0, ToAccount(BOB)),
+			Error::<Test>::CollectionBeingDestroyed
+		);
+		assert_noop!(
+			UNQ::Pallet::<Test>::transfer(Origin::signed(ALICE), COLLECTION_ID_0, 0, BOB),
+			UNQ::Error::<Test>::Locked
+		);
+
+		// Not enough weight for a single step
+		assert_eq!(NFTCore::on_idle(1, 0), 0);
+		assert!(NFTCore::nfts(COLLECTION_ID_0, 0).is_some());
+
+		NFTCore::on_idle(1, Weight::max_value());
+		assert_eq!(NFTCore::nfts(COLLECTION_ID_0, 0), None);
+		assert_eq!(NFTCore::nfts(COLLECTION_ID_0, 1), None);
+		assert_eq!(NFTCore::children((COLLECTION_ID_0, 0), (COLLECTION_ID_0, 1)), None);
+		assert_eq!(NFTCore::properties((COLLECTION_ID_0, None::<u32>, stbk("name"))), None);
+		assert_eq!(NFTCore::collections(COLLECTION_ID_0), None);
+		assert!(!DestroyingCollections::<Test>::contains_key(COLLECTION_ID_0));
+		assert_eq!(Balances::reserved_balance(&ALICE), 0);
+	});
+}
+
+#[test]
+fn burn_collection_stays_within_weight() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_collection();
+		basic_collection();
+		for collection_id in [COLLECTION_ID_0, COLLECTION_ID_1] {
+			assert_ok!(NFTCore::mint_nft(
+				Origin::signed(ALICE),
+				collection_id,
+				None,
+				Some(ALICE),
+				Some(Permill::from_percent(0)),
+				None,
+				Some(stv("metadata")),
+				true
+			));
+			let witness =
+				UNQ::Pallet::<Test>::class_details(collection_id).unwrap().destroy_witness();
+			assert_ok!(NFTCore::burn_collection(Origin::signed(ALICE), collection_id, witness));
+		}
+
+		// The weight of a single step only burns a single NFT
+		assert!(NFTCore::on_idle(1, 10_000) <= 10_000);
+		assert_eq!(
+			NFTCore::nfts(COLLECTION_ID_0, 0).is_some(),
+			NFTCore::nfts(COLLECTION_ID_1, 0).is_none()
+		);
+
+		NFTCore::on_idle(2, Weight::max_value());
+		assert_eq!(NFTCore::collections(COLLECTION_ID_0), None);
+		assert_eq!(NFTCore::collections(COLLECTION_ID_1), None);
+	});
+}
+
+#[test]
+fn burn_collection_keeps_nfts_of_other_collections() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_collection();
+		basic_mint();
+		basic_mint();
+		assert_ok!(NFTCore::create_collection(
+			Origin::signed(BOB),
+			stv("metadata"),
+			None,
+			stv("SYM"),
+			None
+		));
+		assert_ok!(NFTCore::mint_nft(
+			Origin::signed(BOB),
+			COLLECTION_ID_1,
+			Some(ALICE),
+			Some(BOB),
+			Some(Permill::from_percent(0)),
+			None,
+			Some(stv("metadata")),
+			true
+		));
+		// The NFT of BOB is nested under NFT 1 of the collection, which is nested under NFT 0
+		assert_ok!(NFTCore::send(
+			Origin::signed(ALICE),
+			COLLECTION_ID_1,
+			0,
+			ToNft(COLLECTION_ID_0, 1)
+		));
+		assert_ok!(NFTCore::send(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			1,
+			ToNft(COLLECTION_ID_0, 0)
+		));
+		assert_ok!(NFTCore::burn_collection(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			collection_witness()
+		));
+
+		// The admin of the destroyed collection does not get to burn the NFT of BOB
+		NFTCore::on_idle(1, Weight::max_value());
+		assert_eq!(NFTCore::collections(COLLECTION_ID_0), None);
+		assert!(NFTCore::nfts(COLLECTION_ID_1, 0).is_some());
+		assert_eq!(NFTCore::parent(COLLECTION_ID_1, 0), None);
+		assert_eq!(NFTCore::root_owner(COLLECTION_ID_1, 0).unwrap(), ALICE);
+		assert_ok!(NFTCore::send(Origin::signed(ALICE), COLLECTION_ID_1, 0, ToAccount(CHARLIE)));
+	});
+}
+
+#[test]
+fn burn_collection_waits_for_locked_nfts() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_collection();
+		basic_mint();
+		basic_mint();
+		assert_ok!(<NFTCore as NftLock<_, _>>::lock(COLLECTION_ID_0, 0));
+		assert_ok!(NFTCore::burn_collection(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			collection_witness()
+		));
+
+		// The locked NFT is kept, and so is the collection
+		NFTCore::on_idle(1, Weight::max_value());
+		assert!(NFTCore::nfts(COLLECTION_ID_0, 0).is_some());
+		assert_eq!(NFTCore::nfts(COLLECTION_ID_0, 1), None);
+		assert!(NFTCore::collections(COLLECTION_ID_0).is_some());
+		assert!(DestroyingCollections::<Test>::contains_key(COLLECTION_ID_0));
+
+		<NFTCore as NftLock<_, _>>::unlock(COLLECTION_ID_0, 0);
+		NFTCore::on_idle(2, Weight::max_value());
+		assert_eq!(NFTCore::nfts(COLLECTION_ID_0, 0), None);
+		assert_eq!(NFTCore::collections(COLLECTION_ID_0), None);
+		assert!(!DestroyingCollections::<Test>::contains_key(COLLECTION_ID_0));
+	});
+}
+
+#[test]
+fn deposits_are_reserved_and_refunded() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_collection();
+		basic_mint();
+		assert_ok!(NFTCore::add_resource(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			0,
+			0,
+			basic_resource()
+		));
+		let resource_deposit = metadata_deposit(basic_resource().encoded_size() as u64);
+		assert_eq!(
+			NFTCore::collections(COLLECTION_ID_0).and_then(|collection| collection.deposit),
+			Some(Deposit { account: ALICE, amount: metadata_deposit(11) })
+		);
+		assert_eq!(
+			NFTCore::nfts(COLLECTION_ID_0, 0).and_then(|nft| nft.deposit),
+			Some(Deposit { account: ALICE, amount: metadata_deposit(8) })
+		);
+		assert_eq!(
+			NFTCore::resources((COLLECTION_ID_0, 0, 0)).and_then(|(_, deposit)| deposit),
+			Some(Deposit { account: ALICE, amount: resource_deposit })
+		);
+		let reserved = Balances::reserved_balance(&ALICE);
+		assert_ok!(NFTCore::remove_resource(Origin::signed(ALICE), COLLECTION_ID_0, 0, 0));
+		assert_eq!(Balances::reserved_balance(&ALICE), reserved - resource_deposit);
+		// Burning an NFT refunds the deposits of its resources as well
+		assert_ok!(NFTCore::add_resource(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			0,
+			0,
+			basic_resource()
+		));
+		assert_ok!(NFTCore::burn_nft(Origin::signed(ALICE), COLLECTION_ID_0, 0));
+		assert_eq!(Balances::reserved_balance(&ALICE), ClassDeposit::get() + metadata_deposit(11));
+	});
+}
+
+#[test]
+fn redeposit_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_collection();
+		basic_mint();
+		assert_ok!(NFTCore::add_resource(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			0,
+			0,
+			basic_resource()
+		));
+		// Pretend the deposits were taken while the deposit constants were higher
+		assert_ok!(Balances::reserve(&ALICE, 300));
+		Collections::<Test>::mutate(COLLECTION_ID_0, |collection| {
+			collection.as_mut().unwrap().deposit.as_mut().unwrap().amount += 100
+		});
+		NFTs::<Test>::mutate(COLLECTION_ID_0, 0, |nft| {
+			nft.as_mut().unwrap().deposit.as_mut().unwrap().amount += 100
+		});
+		Resources::<Test>::mutate((COLLECTION_ID_0, 0, 0), |resource| {
+			resource.as_mut().unwrap().1.as_mut().unwrap().amount += 100
+		});
+		let reserved = Balances::reserved_balance(&ALICE);
+
+		// Deposits of other accounts are left alone
+		assert_ok!(NFTCore::redeposit(Origin::signed(BOB), COLLECTION_ID_0, vec![0], vec![(0, 0)]));
+		assert_eq!(Balances::reserved_balance(&ALICE), reserved);
+
+		assert_ok!(NFTCore::redeposit(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			vec![0, 1],
+			vec![(0, 0), (0, 1)]
+		));
+		assert_eq!(Balances::reserved_balance(&ALICE), reserved - 300);
+		assert_eq!(
+			NFTCore::nfts(COLLECTION_ID_0, 0)
+				.and_then(|nft| nft.deposit)
+				.map(|deposit| deposit.amount),
+			Some(metadata_deposit(8))
+		);
+
+		// The collection owner may recalculate deposits paid by other accounts
+		assert_ok!(NFTCore::change_issuer(Origin::signed(ALICE), COLLECTION_ID_0, BOB));
+		assert_ok!(NFTCore::accept_issuer(Origin::signed(BOB), COLLECTION_ID_0));
+		assert_ok!(NFTCore::mint_nft(
+			Origin::signed(BOB),
+			COLLECTION_ID_0,
+			None,
+			Some(BOB),
+			Some(Permill::from_percent(0)),
+			None,
+			Some(stv("metadata")),
+			true
+		));
+		// Pretend the deposit of BOB was taken while the deposit constants were lower
+		Balances::unreserve(&BOB, 50);
+		NFTs::<Test>::mutate(COLLECTION_ID_0, 1, |nft| {
+			nft.as_mut().unwrap().deposit.as_mut().unwrap().amount -= 50
+		});
+		let reserved_bob = Balances::reserved_balance(&BOB);
+		assert_ok!(NFTCore::redeposit(Origin::signed(CHARLIE), COLLECTION_ID_0, vec![1], vec![]));
+		assert_eq!(Balances::reserved_balance(&BOB), reserved_bob);
+		assert_ok!(NFTCore::redeposit(Origin::signed(ALICE), COLLECTION_ID_0, vec![1], vec![]));
+		assert_eq!(Balances::reserved_balance(&BOB), reserved_bob + 50);
+		assert_eq!(
+			NFTCore::nfts(COLLECTION_ID_0, 1).and_then(|nft| nft.deposit),
+			Some(Deposit { account: BOB, amount: metadata_deposit(8) })
+		);
+		assert_noop!(
+			NFTCore::redeposit(Origin::signed(ALICE), COLLECTION_ID_1, vec![], vec![]),
+			Error::<Test>::CollectionUnknown
+		);
+	});
+}
+
+#[test]
+fn protocol_origin_mints_to_explicit_owner() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			NFTCore::create_collection(Origin::root(), stv("metadata"), None, stv("SYM"), None),
+			Error::<Test>::OwnerNotSet
+		);
+		assert_noop!(
+			NFTCore::create_collection(
+				Origin::signed(ALICE),
+				stv("metadata"),
+				None,
+				stv("SYM"),
+				Some(BOB)
+			),
+			Error::<Test>::NoPermission
+		);
+		assert_ok!(NFTCore::create_collection(
+			Origin::root(),
+			stv("metadata"),
+			None,
+			stv("SYM"),
+			Some(BOB)
+		));
+		assert_eq!(NFTCore::collection_issuer(COLLECTION_ID_0), Ok(BOB));
+		// The protocol origin pays no deposits
+		assert_eq!(Balances::reserved_balance(&BOB), 0);
+
+		assert_noop!(
+			NFTCore::mint_nft(
+				Origin::root(),
+				COLLECTION_ID_0,
+				None,
+				Some(BOB),
+				Some(Permill::from_percent(0)),
+				None,
+				Some(stv("metadata")),
+				true
+			),
+			Error::<Test>::RecipientNotSet
+		);
+		assert_ok!(NFTCore::mint_nft(
+			Origin::root(),
+			COLLECTION_ID_0,
+			Some(CHARLIE),
+			Some(BOB),
+			Some(Permill::from_percent(0)),
+			None,
+			Some(stv("metadata")),
+			true
+		));
+		assert_eq!(UNQ::Pallet::<Test>::owner(COLLECTION_ID_0, 0), Some(CHARLIE));
+		// Signed origins may mint directly to a recipient as well
+		assert_ok!(NFTCore::mint_nft(
+			Origin::signed(BOB),
+			COLLECTION_ID_0,
+			Some(ALICE),
+			Some(BOB),
+			Some(Permill::from_percent(0)),
+			None,
+			Some(stv("metadata")),
+			true
+		));
+		assert_eq!(UNQ::Pallet::<Test>::owner(COLLECTION_ID_0, 1), Some(ALICE));
+	});
+}
+
+#[test]
+fn mint_modes_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_collection();
+		let mint_by = |who: AccountId32| {
+			NFTCore::mint_nft(
+				Origin::signed(who.clone()),
+				COLLECTION_ID_0,
+				None,
+				Some(who),
+				Some(Permill::from_percent(0)),
+				None,
+				Some(stv("metadata")),
+				true,
+			)
+		};
+		// Only the issuer may mint by default
+		assert_noop!(mint_by(BOB), Error::<Test>::NoPermission);
+		assert_noop!(
+			NFTCore::set_mint_settings(Origin::signed(BOB), COLLECTION_ID_0, MintMode::Public, 0),
+			Error::<Test>::NoPermission
+		);
+
+		assert_ok!(NFTCore::set_mint_settings(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			MintMode::Public,
+			500
+		));
+		let free_alice = Balances::free_balance(ALICE);
+		let reserved_alice = Balances::reserved_balance(ALICE);
+		assert_ok!(mint_by(BOB));
+		// The minter reimburses the owner for the instance deposit of `pallet_uniques`
+		assert_eq!(Balances::free_balance(ALICE), free_alice + 500);
+		assert_eq!(Balances::reserved_balance(ALICE), reserved_alice + InstanceDeposit::get());
+		// The issuer mints for free
+		assert_ok!(mint_by(ALICE));
+		assert_eq!(
+			Balances::free_balance(ALICE),
+			free_alice + 500 - InstanceDeposit::get() - metadata_deposit(8)
+		);
+
+		assert_ok!(NFTCore::set_mint_settings(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			MintMode::Allowlist,
+			500
+		));
+		assert_noop!(mint_by(BOB), Error::<Test>::NoPermission);
+		assert_ok!(NFTCore::set_mint_allowlist(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			vec![BOB, CHARLIE],
+			vec![]
+		));
+		assert_ok!(mint_by(BOB));
+		assert_ok!(NFTCore::set_mint_allowlist(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			vec![],
+			vec![BOB]
+		));
+		assert_noop!(mint_by(BOB), Error::<Test>::NoPermission);
+		// A batch pays the mint price of every NFT
+		let free_charlie = Balances::free_balance(CHARLIE);
+		assert_ok!(NFTCore::mint_nft_batch(
+			Origin::signed(CHARLIE),
+			COLLECTION_ID_0,
+			None,
+			vec![(CHARLIE, Permill::from_percent(0), stv("metadata")); 2]
+		));
+		assert_eq!(
+			Balances::free_balance(CHARLIE),
+			free_charlie - 2 * 500 - 2 * InstanceDeposit::get() - 2 * metadata_deposit(8)
+		);
+	});
+}
+
+/// Hashes two nodes of a presale Merkle tree in ascending order
+fn hash_pair(a: MerkleHash, b: MerkleHash) -> MerkleHash {
+	if a <= b {
+		BlakeTwo256::hash_of(&(a, b))
+	} else {
+		BlakeTwo256::hash_of(&(b, a))
+	}
+}
+
+#[test]
+fn mint_with_proof_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		basic_collection();
+		let leaves = [
+			NFTCore::presale_leaf(&BOB, 2),
+			NFTCore::presale_leaf(&CHARLIE, 1),
+			NFTCore::presale_leaf(&ALICE, 1),
+			NFTCore::presale_leaf(&CHARLIE, 5),
+		];
+		let right = hash_pair(leaves[2], leaves[3]);
+		let root = hash_pair(hash_pair(leaves[0], leaves[1]), right);
+		let bob_proof: BoundedVec<_, MaxProofLength> = vec![leaves[1], right].try_into().unwrap();
+		let charlie_proof: BoundedVec<_, MaxProofLength> =
+			vec![leaves[0], right].try_into().unwrap();
+
+		assert_noop!(
+			NFTCore::mint_with_proof(
+				Origin::signed(BOB),
+				COLLECTION_ID_0,
+				2,
+				bob_proof.clone(),
+				stv("metadata")
+			),
+			Error::<Test>::PresaleUnknown
+		);
+		assert_noop!(
+			NFTCore::set_presale(Origin::signed(BOB), COLLECTION_ID_0, root, 5, 10),
+			Error::<Test>::NoPermission
+		);
+		assert_noop!(
+			NFTCore::set_presale(Origin::signed(ALICE), COLLECTION_ID_0, root, 10, 5),
+			Error::<Test>::NotInRange
+		);
+		assert_ok!(NFTCore::set_presale(Origin::signed(ALICE), COLLECTION_ID_0, root, 5, 10));
+		assert_ok!(NFTCore::set_mint_settings(
+			Origin::signed(ALICE),
+			COLLECTION_ID_0,
+			MintMode::Issuer,
+			500
+		));
+		assert_noop!(
+			NFTCore::mint_with_proof(
+				Origin::signed(BOB),
+				COLLECTION_ID_0,
+				2,
+				bob_proof.clone(),
+				stv("metadata")
+			),
+			Error::<Test>::PresaleNotActive
+		);
+
+		System::set_block_number(5);
+		assert_noop!(
+			NFTCore::mint_with_proof(
+				Origin::signed(BOB),
+				COLLECTION_ID_0,
+				3,
+				bob_proof.clone(),
+				stv("metadata")
+			),
+			Error::<Test>::InvalidProof
+		);
+		assert_noop!(
+			NFTCore::mint_with_proof(
+				Origin::signed(CHARLIE),
+				COLLECTION_ID_0,
+				2,
+				bob_proof.clone(),
+				stv("metadata")
+			),
+			Error::<Test>::InvalidProof
+		);
+		let free_alice = Balances::free_balance(ALICE);
+		for _ in 0..2 {
+			assert_ok!(NFTCore::mint_with_proof(
+				Origin::signed(BOB),
+				COLLECTION_ID_0,
+				2,
+				bob_proof.clone(),
+				stv("metadata")
+			));
+		}
+		assert_eq!(UNQ::Pallet::<Test>::owner(COLLECTION_ID_0, 1), Some(BOB));
+		assert_eq!(NFTCore::nfts(COLLECTION_ID_0, 1).map(|nft| nft.author), Some(ALICE));
+		assert_eq!(NFTCore::presale_claims(COLLECTION_ID_0, BOB), 2);
+		assert_eq!(Balances::free_balance(ALICE), free_alice + 2 * 500);
+		assert_noop!(
+			NFTCore::mint_with_proof(
+				Origin::signed(BOB),
+				COLLECTION_ID_0,
+				2,
+				bob_proof.clone(),
+				stv("metadata")
+			),
+			Error::<Test>::PresaleAllowanceExceeded
+		);
+		assert_ok!(NFTCore::mint_with_proof(
+			Origin::signed(CHARLIE),
+			COLLECTION_ID_0,
+			1,
+			charlie_proof.clone(),
+			stv("metadata")
+		));
+
+		System::set_block_number(10);
+		assert_noop!(
+			NFTCore::mint_with_proof(
+				Origin::signed(CHARLIE),
+				COLLECTION_ID_0,
+				1,
+				charlie_proof,
+				stv("metadata")
+			),
+			Error::<Test>::PresaleNotActive
+		);
+	});
+}
+
+#[test]
+fn mint_with_voucher_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		let creator_pair = sr25519::Pair::from_seed(&[7u8; 32]);
+		let creator = MultiSigner::from(creator_pair.public()).into_account();
+		Balances::make_free_balance_be(&creator, 100_000);
+		assert_ok!(NFTCore::create_collection(
+			Origin::signed(creator.clone()),
+			stv("metadata"),
+			None,
+			stv("SYM"),
+			None
+		));
+		let voucher = MintVoucher {
+			collection_id: COLLECTION_ID_0,
+			metadata: stbs("metadata"),
+			price: 1_000,
+			expiry: 10,
+			nonce: 0,
+		};
+		let signature =
+			MultiSignature::from(creator_pair.sign(&NFTCore::voucher_payload(&voucher)));
+
+		let other_pair = sr25519::Pair::from_seed(&[8u8; 32]);
+		assert_noop!(
+			NFTCore::mint_with_voucher(
+				Origin::signed(BOB),
+				voucher.clone(),
+				MultiSignature::from(other_pair.sign(&NFTCore::voucher_payload(&voucher)))
+			),
+			Error::<Test>::InvalidSignature
+		);
+		assert_noop!(
+			NFTCore::mint_with_voucher(
+				Origin::signed(BOB),
+				MintVoucher { price: 0, ..voucher.clone() },
+				signature.clone()
+			),
+			Error::<Test>::InvalidSignature
+		);
+
+		let free_creator = Balances::free_balance(&creator);
+		assert_eq!(NFTCore::voucher_prune_cursor(), None);
+		assert_ok!(NFTCore::mint_with_voucher(
+			Origin::signed(BOB),
+			voucher.clone(),
+			signature.clone()
+		));
+		// Pruning starts from the block of the first redeemed voucher
+		assert_eq!(NFTCore::voucher_prune_cursor(), Some(1));
+		assert_eq!(UNQ::Pallet::<Test>::owner(COLLECTION_ID_0, 0), Some(BOB));
+		assert_eq!(NFTCore::nfts(COLLECTION_ID_0, 0).map(|nft| nft.author), Some(creator.clone()));
+		assert_eq!(Balances::free_balance(&creator), free_creator + 1_000);
+		assert_noop!(
+			NFTCore::mint_with_voucher(Origin::signed(CHARLIE), voucher.clone(), signature),
+			Error::<Test>::VoucherAlreadyUsed
+		);
+		// A signature over the bare voucher is not accepted
+		let voucher = MintVoucher { nonce: 2, ..voucher };
+		assert_noop!(
+			NFTCore::mint_with_voucher(
+				Origin::signed(CHARLIE),
+				voucher.clone(),
+				MultiSignature::from(creator_pair.sign(&voucher.encode()))
+			),
+			Error::<Test>::InvalidSignature
+		);
+
+		// Used vouchers are pruned once expired
+		System::set_block_number(9);
+		NFTCore::on_idle(9, Weight::max_value());
+		assert_eq!(NFTCore::used_vouchers(10, (COLLECTION_ID_0, 0)), Some(()));
+		assert_eq!(NFTCore::voucher_prune_cursor(), Some(10));
+		System::set_block_number(10);
+		// Nothing is pruned without the weight for a step
+		assert_eq!(NFTCore::on_idle(10, 0), 0);
+		assert_eq!(NFTCore::voucher_prune_cursor(), Some(10));
+		NFTCore::on_idle(10, Weight::max_value());
+		assert_eq!(NFTCore::used_vouchers(10, (COLLECTION_ID_0, 0)), None);
+		assert_eq!(NFTCore::voucher_prune_cursor(), Some(11));
+
+		let voucher = MintVoucher { nonce: 1, ..voucher };
+		let signature =
+			MultiSignature::from(creator_pair.sign(&NFTCore::voucher_payload(&voucher)));
+		assert_noop!(
+			NFTCore::mint_with_voucher(Origin::signed(CHARLIE), voucher, signature),
+			Error::<Test>::VoucherExpired
+		);
+	});
+}
+
+#[test]
+fn migrate_to_v1_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		StorageVersion::new(0).put::<NFTCore>();
+		// A collection and an NFT with only their metadata and a royalty of 5%
+		unhashed::put(&Collections::<Test>::hashed_key_for(COLLECTION_ID_0), &stv("metadata"));
+		unhashed::put(
+			&NFTs::<Test>::hashed_key_for(COLLECTION_ID_0, 0),
+			&(ALICE, 5u8, stv("metadata")),
+		);
+		let resource_key = [Resources::<Test>::final_prefix().to_vec(), vec![0; 8]].concat();
+		unhashed::put(&resource_key, &(ALICE, 5u8, stv("metadata")));
+
+		NFTCore::on_runtime_upgrade();
+		assert_eq!(NFTCore::on_chain_storage_version(), 1);
+		let collection = NFTCore::collections(COLLECTION_ID_0).unwrap();
+		assert_eq!(collection.metadata, stbs("metadata"));
+		assert_eq!(collection.max, None);
+		assert_eq!(collection.mint_mode, MintMode::Issuer);
+		assert_eq!(collection.deposit, None);
+		let nft = NFTCore::nfts(COLLECTION_ID_0, 0).unwrap();
+		assert_eq!(nft.author, ALICE);
+		assert_eq!(nft.royalty, Permill::from_percent(5));
+		assert!(nft.transferable);
+		assert_eq!(unhashed::get_raw(&resource_key), None);
+
+		// Running it again does nothing
+		NFTCore::on_runtime_upgrade();
+		assert_eq!(NFTCore::nfts(COLLECTION_ID_0, 0), Some(nft));
+	});
+}
@@ -0,0 +1,179 @@
+//! Traits through which other pallets interact with nft-core.
+
+use super::*;
+use frame_support::traits::{ExistenceRequirement, Get};
+use pallet_uniques::Locker;
+use sp_runtime::{traits::Saturating, DispatchError, DispatchResult};
+
+/// Pays out the royalty of an NFT when it is sold.
+///
+/// Every sale path (marketplace, auction, swap) should pay through this before handing the
+/// rest of the price to the seller.
+pub trait RoyaltyHandler<AccountId, CollectionId, NftId, Balance> {
+	/// Pay the royalty of an NFT sold for `price` from `buyer` to its author and royalty
+	/// recipients. Returns the part of `price` left for the seller.
+	///
+	/// A share below the existential deposit cannot create the account of its recipient, so
+	/// it goes to the author instead, or is left for the seller if the author's share cannot
+	/// be paid either.
+	fn pay_royalty(
+		buyer: &AccountId,
+		collection_id: CollectionId,
+		nft_id: NftId,
+		price: Balance,
+	) -> Result<Balance, DispatchError>;
+}
+
+impl<T: Config> RoyaltyHandler<T::AccountId, T::CollectionId, T::NftId, BalanceOf<T>>
+	for Pallet<T>
+{
+	fn pay_royalty(
+		buyer: &T::AccountId,
+		collection_id: T::CollectionId,
+		nft_id: T::NftId,
+		price: BalanceOf<T>,
+	) -> Result<BalanceOf<T>, DispatchError> {
+		let nft = Self::nfts(collection_id, nft_id).ok_or(Error::<T>::NftUnknown)?;
+		let royalty = nft.royalty.mul_floor(price);
+		let can_receive = |who: &T::AccountId, amount: BalanceOf<T>| {
+			amount >= T::Currency::minimum_balance() || !T::Currency::total_balance(who).is_zero()
+		};
+		let pay_share = |to: &T::AccountId, amount: BalanceOf<T>| {
+			if amount.is_zero() || buyer == to {
+				return Ok(())
+			}
+			T::Currency::transfer(buyer, to, amount, ExistenceRequirement::AllowDeath)
+		};
+
+		let mut remainder = royalty;
+		let recipients = nft.royalty_recipients.map(|r| r.into_inner()).unwrap_or_default();
+		for (recipient, share) in recipients {
+			let amount = share.mul_floor(royalty).min(remainder);
+			if can_receive(&recipient, amount) {
+				pay_share(&recipient, amount)?;
+				remainder = remainder.saturating_sub(amount);
+			}
+		}
+		let paid = if can_receive(&nft.author, remainder) {
+			pay_share(&nft.author, remainder)?;
+			royalty
+		} else {
+			royalty.saturating_sub(remainder)
+		};
+
+		if !paid.is_zero() {
+			Self::deposit_event(Event::RoyaltyPaid(collection_id, nft_id, paid));
+		}
+		Ok(price.saturating_sub(paid))
+	}
+}
+
+/// Locks NFTs against being sent or burned, e.g. while they are listed for sale or held in
+/// escrow by another pallet.
+///
+/// A lock covers the NFTs nested under the locked NFT as well, as they move along with it.
+pub trait NftLock<CollectionId, NftId> {
+	/// Lock an NFT. Fails if the NFT does not exist, or if it, an NFT it is nested under or an
+	/// NFT nested under it is locked already.
+	fn lock(collection_id: CollectionId, nft_id: NftId) -> DispatchResult;
+
+	/// Unlock an NFT, if it is locked.
+	fn unlock(collection_id: CollectionId, nft_id: NftId);
+
+	/// Returns whether an NFT is locked, either itself or through an NFT it is nested under.
+	fn is_locked(collection_id: CollectionId, nft_id: NftId) -> bool;
+}
+
+impl<T: Config> NftLock<T::CollectionId, T::NftId> for Pallet<T> {
+	fn lock(collection_id: T::CollectionId, nft_id: T::NftId) -> DispatchResult {
+		ensure!(NFTs::<T>::contains_key(collection_id, nft_id), Error::<T>::NftUnknown);
+		Self::ensure_not_locked(collection_id, nft_id)?;
+		let is_tree_locked = Self::descendants(collection_id, nft_id).into_iter().any(
+			|(child_collection_id, child_nft_id)| {
+				Locks::<T>::contains_key(child_collection_id, child_nft_id)
+			},
+		);
+		ensure!(!is_tree_locked, Error::<T>::NftLocked);
+		Locks::<T>::insert(collection_id, nft_id, ());
+		Ok(())
+	}
+
+	fn unlock(collection_id: T::CollectionId, nft_id: T::NftId) {
+		Locks::<T>::remove(collection_id, nft_id);
+	}
+
+	fn is_locked(collection_id: T::CollectionId, nft_id: T::NftId) -> bool {
+		Self::ensure_not_locked(collection_id, nft_id).is_err()
+	}
+}
+
+/// Mints NFTs on behalf of other pallets, e.g. when an NFT of a drop is bought.
+pub trait NftMint<AccountId, CollectionId, NftId, BoundedString> {
+	/// Mint an NFT authored by the collection issuer, without a royalty, to `owner`, who pays
+	/// its instance deposit and metadata deposit. Returns the id of the minted NFT.
+	fn mint(
+		owner: AccountId,
+		collection_id: CollectionId,
+		metadata: BoundedString,
+	) -> Result<NftId, DispatchError>;
+}
+
+impl<T: Config>
+	NftMint<
+		T::AccountId,
+		T::CollectionId,
+		T::NftId,
+		BoundedVec<u8, <T as pallet_uniques::Config>::StringLimit>,
+	> for Pallet<T>
+{
+	fn mint(
+		owner: T::AccountId,
+		collection_id: T::CollectionId,
+		metadata: BoundedVec<u8, <T as pallet_uniques::Config>::StringLimit>,
+	) -> Result<T::NftId, DispatchError> {
+		let class_details = pallet_uniques::Pallet::<T>::class_details(collection_id.into())
+			.ok_or(Error::<T>::CollectionUnknown)?;
+		Self::reimburse_instance_deposits(&class_details, &owner, 1)?;
+		let author = Self::collection_issuer(collection_id)?;
+		let deposit = Self::reserve_deposit(
+			Some(owner.clone()),
+			T::MetadataDepositBase::get(),
+			metadata.len(),
+		)?;
+		let nft_id = Self::do_mint_nft(
+			collection_id,
+			owner.clone(),
+			InstanceInfo {
+				author,
+				royalty: Permill::zero(),
+				royalty_recipients: None,
+				metadata,
+				transferable: true,
+				deposit,
+			},
+		)?;
+
+		Self::deposit_event(Event::NftMinted(owner, collection_id, nft_id));
+		Ok(nft_id)
+	}
+}
+
+/// Keeps soulbound and locked NFTs, NFTs of collections being destroyed and NFTs which are
+/// nested or have NFTs nested under them from being transferred or burned through the calls of
+/// `pallet_uniques`, which would bypass the nesting tree of nft-core. NFTs are never burned
+/// through `pallet_uniques`, so that `burn_nft` cleans up their nft-core data.
+impl<T: Config> Locker<T::ClassId, T::InstanceId> for Pallet<T> {
+	fn is_locked(class: T::ClassId, instance: T::InstanceId) -> bool {
+		let collection_id = T::CollectionId::from(class);
+		let nft_id = T::NftId::from(instance);
+		DestroyingCollections::<T>::contains_key(collection_id) ||
+			Locks::<T>::contains_key(collection_id, nft_id) ||
+			Self::nfts(collection_id, nft_id).map_or(false, |nft| !nft.transferable) ||
+			Parent::<T>::contains_key(collection_id, nft_id) ||
+			Children::<T>::iter_key_prefix((collection_id, nft_id)).next().is_some()
+	}
+
+	fn is_burn_locked(class: T::ClassId, instance: T::InstanceId) -> bool {
+		NFTs::<T>::contains_key(T::CollectionId::from(class), T::NftId::from(instance))
+	}
+}
@@ -4,21 +4,213 @@ use frame_support::pallet_prelude::*;
 use serde::{Deserialize, Serialize};
 
 use scale_info::TypeInfo;
+use sp_runtime::Permill;
+
+pub type BaseId = u32;
+pub type PartId = u32;
+pub type SlotId = u32;
 
 #[derive(Encode, Decode, Eq, Copy, PartialEq, Clone, RuntimeDebug, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
-pub struct ClassInfo<BoundedString> {
+pub struct ClassInfo<BoundedString, AccountId, Balance> {
 	/// Arbitrary data about a class, e.g. IPFS hash
 	pub metadata: BoundedString,
+	/// The maximum number of instances which may be minted, if any
+	pub max: Option<u32>,
+	/// The ticker symbol of the class
+	pub symbol: BoundedString,
+	/// Who besides the issuer may mint instances
+	pub mint_mode: MintMode,
+	/// The price of minting an instance, paid to the owner of the class by minters other than
+	/// the issuer
+	pub mint_price: Balance,
+	/// The deposit reserved for storing the metadata and symbol, if one was taken
+	pub deposit: Option<Deposit<AccountId, Balance>>,
 }
 
 #[derive(Encode, Decode, Eq, Copy, PartialEq, Clone, RuntimeDebug, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
-pub struct InstanceInfo<AccountId, BoundedString> {
+pub struct InstanceInfo<AccountId, BoundedString, BoundedRecipients, Balance> {
 	/// The user account which receives the royalty
 	pub author: AccountId,
-	/// Royalty in percent in range 0-99
-	pub royalty: u8,
+	/// Share of each sale paid as royalty, below 100%
+	pub royalty: Permill,
+	/// Accounts receiving a share of the royalty, the remainder goes to the author
+	pub royalty_recipients: Option<BoundedRecipients>,
 	/// Arbitrary data about an instance, e.g. IPFS hash
 	pub metadata: BoundedString,
+	/// Whether the instance may change owner
+	pub transferable: bool,
+	/// The deposit reserved for storing the metadata, if one was taken
+	pub deposit: Option<Deposit<AccountId, Balance>>,
+}
+
+/// Who besides the issuer may mint NFTs into a collection.
+#[derive(Encode, Decode, Eq, Copy, PartialEq, Clone, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum MintMode {
+	/// Only the issuer may mint
+	Issuer,
+	/// Any account may mint
+	Public,
+	/// Only the accounts on the allowlist of the collection may mint
+	Allowlist,
+}
+
+/// Who set a property, as the root owner of an NFT may set its properties besides the issuer.
+#[derive(Encode, Decode, Eq, Copy, PartialEq, Clone, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum PropertySetter {
+	/// The protocol origin
+	Protocol,
+	/// The issuer of the collection
+	Issuer,
+	/// The root owner of the NFT
+	Owner,
+}
+
+/// A phase in which the accounts of a Merkle tree of `(account, quantity)` leaves may mint up to
+/// `quantity` NFTs each.
+#[derive(Encode, Decode, Eq, Copy, PartialEq, Clone, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Presale<Hash, BlockNumber> {
+	/// The root of the Merkle tree of allowed accounts
+	pub merkle_root: Hash,
+	/// The first block of the presale
+	pub start: BlockNumber,
+	/// The block at which the presale is over
+	pub end: BlockNumber,
+}
+
+/// An offer signed off-chain by the issuer of a collection to mint an NFT to whoever redeems it
+/// first.
+#[derive(Encode, Decode, Eq, Copy, PartialEq, Clone, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct MintVoucher<CollectionId, BoundedString, Balance, BlockNumber> {
+	/// The collection the NFT is minted into
+	pub collection_id: CollectionId,
+	/// Arbitrary data about the NFT, e.g. IPFS hash
+	pub metadata: BoundedString,
+	/// The price paid to the issuer by whoever redeems the voucher
+	pub price: Balance,
+	/// The block at which the voucher expires
+	pub expiry: BlockNumber,
+	/// A number unique among the vouchers of the collection with the same expiry, so that each
+	/// can be redeemed once
+	pub nonce: u64,
+}
+
+/// The owner of an NFT: either an account or another NFT which it is nested under.
+#[derive(Encode, Decode, Eq, Copy, PartialEq, Clone, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum AccountIdOrCollectionNftTuple<AccountId, CollectionId, NftId> {
+	AccountId(AccountId),
+	CollectionAndNftTuple(CollectionId, NftId),
+}
+
+/// A deposit reserved from an account for storing data on chain.
+#[derive(Encode, Decode, Eq, Copy, PartialEq, Clone, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Deposit<AccountId, Balance> {
+	/// The account the deposit is reserved from
+	pub account: AccountId,
+	/// The amount reserved
+	pub amount: Balance,
+}
+
+#[derive(Encode, Decode, Eq, PartialEq, Clone, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ResourceInfo<BoundedString, BoundedParts> {
+	/// URI of the media of the resource, e.g. IPFS hash
+	pub src: Option<BoundedString>,
+	/// Arbitrary data about the resource, e.g. IPFS hash
+	pub metadata: Option<BoundedString>,
+	/// URI of the license of the media
+	pub license: Option<BoundedString>,
+	/// URI of a thumbnail of the media
+	pub thumb: Option<BoundedString>,
+	/// The base the resource is composed from, if any
+	pub base: Option<BaseId>,
+	/// The parts of `base` which make up the resource
+	pub parts: Option<BoundedParts>,
+	/// The slot of a base the resource can be equipped into, if any
+	pub slot: Option<SlotId>,
+	/// Whether the resource is still waiting to be accepted by the owner of the instance
+	pub pending: bool,
+}
+
+#[derive(Encode, Decode, Eq, Copy, PartialEq, Clone, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct BaseInfo<AccountId, BoundedString, Balance> {
+	/// The user account which manages the base
+	pub issuer: AccountId,
+	/// The kind of media the base is made of, e.g. "svg"
+	pub base_type: BoundedString,
+	/// The ticker symbol of the base
+	pub symbol: BoundedString,
+	/// The deposit reserved for storing the base type, symbol and parts, if one was taken
+	pub deposit: Option<Deposit<AccountId, Balance>>,
+}
+
+/// The collections whose NFTs may be equipped into a slot part.
+#[derive(Encode, Decode, Eq, Copy, PartialEq, Clone, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum EquippableList<BoundedCollectionList> {
+	All,
+	Empty,
+	Custom(BoundedCollectionList),
+}
+
+/// A part of a base which is always rendered.
+#[derive(Encode, Decode, Eq, Copy, PartialEq, Clone, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct FixedPart<BoundedString> {
+	/// The id of the part, unique within the base
+	pub id: PartId,
+	/// The order in which the part is rendered, higher on top
+	pub z: u32,
+	/// URI of the media of the part
+	pub src: BoundedString,
+}
+
+/// A part of a base into which NFTs can be equipped.
+#[derive(Encode, Decode, Eq, Copy, PartialEq, Clone, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct SlotPart<BoundedString, BoundedCollectionList> {
+	/// The id of the part, unique within the base
+	pub id: PartId,
+	/// The collections whose NFTs may be equipped into the slot
+	pub equippable: EquippableList<BoundedCollectionList>,
+	/// The order in which the part is rendered, higher on top
+	pub z: u32,
+	/// URI of the media rendered while the slot is empty
+	pub src: BoundedString,
+}
+
+#[derive(Encode, Decode, Eq, Copy, PartialEq, Clone, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum PartType<BoundedString, BoundedCollectionList> {
+	FixedPart(FixedPart<BoundedString>),
+	SlotPart(SlotPart<BoundedString, BoundedCollectionList>),
+}
+
+impl<BoundedString, BoundedCollectionList> PartType<BoundedString, BoundedCollectionList> {
+	pub fn id(&self) -> PartId {
+		match self {
+			PartType::FixedPart(part) => part.id,
+			PartType::SlotPart(part) => part.id,
+		}
+	}
+}
+
+/// A property of a base theme, e.g. a color or a font.
+#[derive(Encode, Decode, Eq, Copy, PartialEq, Clone, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ThemeProperty<BoundedKey, BoundedValue> {
+	/// The key of the property, e.g. "primary_color"
+	pub key: BoundedKey,
+	/// The value of the property, e.g. "#ff00ff"
+	pub value: BoundedValue,
+	/// Whether parts equipped into the base apply the property as well
+	pub inherit: bool,
 }
@@ -22,7 +22,7 @@ use frame_support::{ensure, traits::Get};
 use sp_runtime::{DispatchError, DispatchResult};
 
 impl<T: Config<I>, I: 'static> Pallet<T, I> {
-	pub(crate) fn do_transfer(
+	pub fn do_transfer(
 		class: T::ClassId,
 		instance: T::InstanceId,
 		dest: T::AccountId,
@@ -80,7 +80,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		Ok(())
 	}
 
-	pub(super) fn do_destroy_class(
+	pub fn do_destroy_class(
 		class: T::ClassId,
 		witness: DestroyWitness,
 		maybe_check_owner: Option<T::AccountId>,
@@ -150,7 +150,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		Ok(())
 	}
 
-	pub(super) fn do_burn(
+	pub fn do_burn(
 		class: T::ClassId,
 		instance: T::InstanceId,
 		with_details: impl FnOnce(&ClassDetailsFor<T, I>, &InstanceDetailsFor<T, I>) -> DispatchResult,
@@ -177,4 +177,54 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		Self::deposit_event(Event::Burned(class, instance, owner));
 		Ok(())
 	}
+
+	pub fn do_transfer_ownership(
+		class: T::ClassId,
+		owner: T::AccountId,
+		maybe_check_owner: Option<T::AccountId>,
+	) -> DispatchResult {
+		Class::<T, I>::try_mutate(class, |maybe_details| {
+			let details = maybe_details.as_mut().ok_or(Error::<T, I>::Unknown)?;
+			if let Some(check_owner) = maybe_check_owner {
+				ensure!(check_owner == details.owner, Error::<T, I>::NoPermission);
+			}
+			if details.owner == owner {
+				return Ok(())
+			}
+
+			// Move the deposit to the new owner.
+			T::Currency::repatriate_reserved(
+				&details.owner,
+				&owner,
+				details.total_deposit,
+				Reserved,
+			)?;
+			details.owner = owner.clone();
+
+			Self::deposit_event(Event::OwnerChanged(class, owner));
+			Ok(())
+		})
+	}
+
+	pub fn do_set_team(
+		class: T::ClassId,
+		issuer: T::AccountId,
+		admin: T::AccountId,
+		freezer: T::AccountId,
+		maybe_check_owner: Option<T::AccountId>,
+	) -> DispatchResult {
+		Class::<T, I>::try_mutate(class, |maybe_details| {
+			let details = maybe_details.as_mut().ok_or(Error::<T, I>::Unknown)?;
+			if let Some(check_owner) = maybe_check_owner {
+				ensure!(check_owner == details.owner, Error::<T, I>::NoPermission);
+			}
+
+			details.issuer = issuer.clone();
+			details.admin = admin.clone();
+			details.freezer = freezer.clone();
+
+			Self::deposit_event(Event::TeamChanged(class, issuer, admin, freezer));
+			Ok(())
+		})
+	}
 }
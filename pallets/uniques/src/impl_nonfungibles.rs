@@ -19,6 +19,7 @@
 
 use super::*;
 use frame_support::{
+	ensure,
 	traits::{tokens::nonfungibles::*, Get},
 	BoundedSlice,
 };
@@ -129,6 +130,7 @@ impl<T: Config<I>, I: 'static> Mutate<<T as SystemConfig>::AccountId> for Pallet
 	}
 
 	fn burn_from(class: &Self::ClassId, instance: &Self::InstanceId) -> DispatchResult {
+		ensure!(!T::Locker::is_burn_locked(class.clone(), instance.clone()), Error::<T, I>::Locked);
 		Self::do_burn(class.clone(), instance.clone(), |_, _| Ok(()))
 	}
 }
@@ -139,6 +141,7 @@ impl<T: Config<I>, I: 'static> Transfer<T::AccountId> for Pallet<T, I> {
 		instance: &Self::InstanceId,
 		destination: &T::AccountId,
 	) -> DispatchResult {
+		ensure!(!T::Locker::is_locked(class.clone(), instance.clone()), Error::<T, I>::Locked);
 		Self::do_transfer(class.clone(), instance.clone(), destination.clone(), |_, _| Ok(()))
 	}
 }
@@ -114,6 +114,9 @@ pub mod pallet {
 		#[pallet::constant]
 		type ValueLimit: Get<u32>;
 
+		/// Decides which asset instances are locked against transfers and burns.
+		type Locker: Locker<Self::ClassId, Self::InstanceId>;
+
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
 	}
@@ -272,6 +275,8 @@ pub mod pallet {
 		NoDelegate,
 		/// No approval exists that would allow the transfer.
 		Unapproved,
+		/// The asset instance is locked against transfers and burns.
+		Locked,
 	}
 
 	#[pallet::hooks]
@@ -282,6 +287,11 @@ pub mod pallet {
 		pub fn owner(class: T::ClassId, instance: T::InstanceId) -> Option<T::AccountId> {
 			Asset::<T, I>::get(class, instance).map(|i| i.owner)
 		}
+
+		/// Get the details of the asset class, if the class exists.
+		pub fn class_details(class: T::ClassId) -> Option<ClassDetailsFor<T, I>> {
+			Class::<T, I>::get(class)
+		}
 	}
 
 	#[pallet::call]
@@ -445,6 +455,7 @@ pub mod pallet {
 		) -> DispatchResult {
 			let origin = ensure_signed(origin)?;
 			let check_owner = check_owner.map(T::Lookup::lookup).transpose()?;
+			ensure!(!T::Locker::is_burn_locked(class, instance), Error::<T, I>::Locked);
 
 			Self::do_burn(class, instance, |class_details, details| {
 				let is_permitted = class_details.admin == origin || details.owner == origin;
@@ -481,6 +492,7 @@ pub mod pallet {
 		) -> DispatchResult {
 			let origin = ensure_signed(origin)?;
 			let dest = T::Lookup::lookup(dest)?;
+			ensure!(!T::Locker::is_locked(class, instance), Error::<T, I>::Locked);
 
 			Self::do_transfer(class, instance, dest, |class_details, details| {
 				if details.owner != origin && class_details.admin != origin {
@@ -687,25 +699,7 @@ pub mod pallet {
 			let origin = ensure_signed(origin)?;
 			let owner = T::Lookup::lookup(owner)?;
 
-			Class::<T, I>::try_mutate(class, |maybe_details| {
-				let details = maybe_details.as_mut().ok_or(Error::<T, I>::Unknown)?;
-				ensure!(&origin == &details.owner, Error::<T, I>::NoPermission);
-				if details.owner == owner {
-					return Ok(())
-				}
-
-				// Move the deposit to the new owner.
-				T::Currency::repatriate_reserved(
-					&details.owner,
-					&owner,
-					details.total_deposit,
-					Reserved,
-				)?;
-				details.owner = owner.clone();
-
-				Self::deposit_event(Event::OwnerChanged(class, owner));
-				Ok(())
-			})
+			Self::do_transfer_ownership(class, owner, Some(origin))
 		}
 
 		/// Change the Issuer, Admin and Freezer of an asset class.
@@ -733,17 +727,7 @@ pub mod pallet {
 			let admin = T::Lookup::lookup(admin)?;
 			let freezer = T::Lookup::lookup(freezer)?;
 
-			Class::<T, I>::try_mutate(class, |maybe_details| {
-				let details = maybe_details.as_mut().ok_or(Error::<T, I>::Unknown)?;
-				ensure!(&origin == &details.owner, Error::<T, I>::NoPermission);
-
-				details.issuer = issuer.clone();
-				details.admin = admin.clone();
-				details.freezer = freezer.clone();
-
-				Self::deposit_event(Event::TeamChanged(class, issuer, admin, freezer));
-				Ok(())
-			})
+			Self::do_set_team(class, issuer, admin, freezer, Some(origin))
 		}
 
 		/// Approve an instance to be transferred by a delegated third-party account.
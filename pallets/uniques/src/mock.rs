@@ -113,6 +113,7 @@ impl Config for Test {
 	type StringLimit = StringLimit;
 	type KeyLimit = KeyLimit;
 	type ValueLimit = ValueLimit;
+	type Locker = ();
 	type WeightInfo = ();
 }
 
@@ -21,36 +21,36 @@ use super::*;
 use frame_support::{traits::Get, BoundedVec};
 use scale_info::TypeInfo;
 
-pub(super) type DepositBalanceOf<T, I = ()> =
+pub type DepositBalanceOf<T, I = ()> =
 	<<T as Config<I>>::Currency as Currency<<T as SystemConfig>::AccountId>>::Balance;
-pub(super) type ClassDetailsFor<T, I> =
+pub type ClassDetailsFor<T, I> =
 	ClassDetails<<T as SystemConfig>::AccountId, DepositBalanceOf<T, I>>;
-pub(super) type InstanceDetailsFor<T, I> =
+pub type InstanceDetailsFor<T, I> =
 	InstanceDetails<<T as SystemConfig>::AccountId, DepositBalanceOf<T, I>>;
 
 #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
 pub struct ClassDetails<AccountId, DepositBalance> {
 	/// Can change `owner`, `issuer`, `freezer` and `admin` accounts.
-	pub(super) owner: AccountId,
+	pub owner: AccountId,
 	/// Can mint tokens.
-	pub(super) issuer: AccountId,
+	pub issuer: AccountId,
 	/// Can thaw tokens, force transfers and burn tokens from any account.
-	pub(super) admin: AccountId,
+	pub admin: AccountId,
 	/// Can freeze tokens.
-	pub(super) freezer: AccountId,
+	pub freezer: AccountId,
 	/// The total balance deposited for the all storage associated with this asset class. Used by
 	/// `destroy`.
-	pub(super) total_deposit: DepositBalance,
+	pub total_deposit: DepositBalance,
 	/// If `true`, then no deposit is needed to hold instances of this class.
-	pub(super) free_holding: bool,
+	pub free_holding: bool,
 	/// The total number of outstanding instances of this asset class.
-	pub(super) instances: u32,
+	pub instances: u32,
 	/// The total number of outstanding instance metadata of this asset class.
-	pub(super) instance_metadatas: u32,
+	pub instance_metadatas: u32,
 	/// The total number of attributes for this asset class.
-	pub(super) attributes: u32,
+	pub attributes: u32,
 	/// Whether the asset is frozen for non-admin transfers.
-	pub(super) is_frozen: bool,
+	pub is_frozen: bool,
 }
 
 /// Witness data for the destroy transactions.
@@ -81,14 +81,14 @@ impl<AccountId, DepositBalance> ClassDetails<AccountId, DepositBalance> {
 #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, Default, TypeInfo)]
 pub struct InstanceDetails<AccountId, DepositBalance> {
 	/// The owner of this asset.
-	pub(super) owner: AccountId,
+	pub owner: AccountId,
 	/// The approved transferrer of this asset, if one is set.
-	pub(super) approved: Option<AccountId>,
+	pub approved: Option<AccountId>,
 	/// Whether the asset can be transferred or not.
-	pub(super) is_frozen: bool,
+	pub is_frozen: bool,
 	/// The amount held in the pallet's default account for this asset. Free-hold assets will have
 	/// this as zero.
-	pub(super) deposit: DepositBalance,
+	pub deposit: DepositBalance,
 }
 
 #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, Default, TypeInfo)]
@@ -120,3 +120,27 @@ pub struct InstanceMetadata<DepositBalance, StringLimit: Get<u32>> {
 	/// Whether the asset metadata may be changed by a non Force origin.
 	pub(super) is_frozen: bool,
 }
+
+/// Decides whether an asset instance is locked against transfers and burns.
+///
+/// This allows pallets building on top of this one to keep instances in place, e.g. while they
+/// are listed for sale or when they are not meant to be transferable at all. The lock applies to
+/// the `transfer` and `burn` calls and to the `nonfungibles` implementations, while the `do_*`
+/// functions leave such checks to the pallets calling them.
+pub trait Locker<ClassId, InstanceId> {
+	/// Returns `true` if the `instance` of `class` must not be transferred or burned.
+	fn is_locked(class: ClassId, instance: InstanceId) -> bool;
+
+	/// Returns `true` if the `instance` of `class` must not be burned, which is the case for any
+	/// locked instance. Pallets keeping their own data about instances may lock them against
+	/// burns alone, so that they are only burned through the pallet which cleans that data up.
+	fn is_burn_locked(class: ClassId, instance: InstanceId) -> bool {
+		Self::is_locked(class, instance)
+	}
+}
+
+impl<ClassId, InstanceId> Locker<ClassId, InstanceId> for () {
+	fn is_locked(_class: ClassId, _instance: InstanceId) -> bool {
+		false
+	}
+}
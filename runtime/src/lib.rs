@@ -45,6 +45,10 @@ pub use pallet_template;
 
 pub use pallet_nft_core;
 
+pub use pallet_marketplace;
+
+pub use pallet_auction;
+
 /// An index to a block.
 pub type BlockNumber = u32;
 
@@ -107,10 +111,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 100,
+	spec_version: 101,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 };
 
 /// This determines the average expected block time that we are targeting.
@@ -288,13 +292,37 @@ impl pallet_template::Config for Runtime {
 	type Event = Event;
 }
 
+parameter_types! {
+	pub const MaxNestingDepth: u32 = 20;
+	pub const MaxChildren: u32 = 50;
+	pub const PartsLimit: u32 = 10;
+	pub const MaxCollectionsEquippablePerPart: u32 = 10;
+	pub const MaxPropertiesPerTheme: u32 = 20;
+	pub const MaxBatchMint: u32 = 100;
+	pub const MaxPriorities: u32 = 25;
+	pub const MaxRoyaltyRecipients: u32 = 10;
+	pub const MaxProofLength: u32 = 32;
+}
+
 impl pallet_nft_core::Config for Runtime {
-	// type Currency = Balances;
+	type Currency = Balances;
 	type Event = Event;
 	type CollectionId = u32;
 	type NftId = u32;
 	type ResourceId = u32;
 	type ProtocolOrigin = frame_system::EnsureRoot<AccountId>;
+	type MaxNestingDepth = MaxNestingDepth;
+	type MaxChildren = MaxChildren;
+	type PartsLimit = PartsLimit;
+	type MaxCollectionsEquippablePerPart = MaxCollectionsEquippablePerPart;
+	type MaxPropertiesPerTheme = MaxPropertiesPerTheme;
+	type MaxBatchMint = MaxBatchMint;
+	type WeightInfo = pallet_nft_core::weights::SubstrateWeight<Runtime>;
+	type MaxPriorities = MaxPriorities;
+	type MaxRoyaltyRecipients = MaxRoyaltyRecipients;
+	type MaxProofLength = MaxProofLength;
+	type OffchainSignature = Signature;
+	type OffchainPublic = <Signature as Verify>::Signer;
 }
 
 parameter_types! {
@@ -322,9 +350,30 @@ impl pallet_uniques::Config for Runtime {
 	type StringLimit = UniquesStringLimit;
 	type KeyLimit = KeyLimit;
 	type ValueLimit = ValueLimit;
+	type Locker = NFTCore;
 	type WeightInfo = ();
 }
 
+parameter_types! {
+	pub const MaxOffersExpiringPerBlock: u32 = 100;
+}
+
+impl pallet_marketplace::Config for Runtime {
+	type Event = Event;
+	type MaxOffersExpiringPerBlock = MaxOffersExpiringPerBlock;
+}
+
+parameter_types! {
+	pub const AntiSnipingPeriod: BlockNumber = 10;
+	pub const MaxAuctionsEndingPerBlock: u32 = 100;
+}
+
+impl pallet_auction::Config for Runtime {
+	type Event = Event;
+	type AntiSnipingPeriod = AntiSnipingPeriod;
+	type MaxAuctionsEndingPerBlock = MaxAuctionsEndingPerBlock;
+}
+
 // Create the runtime by composing the FRAME pallets that were previously configured.
 construct_runtime!(
 	pub enum Runtime where
@@ -344,6 +393,8 @@ construct_runtime!(
 		TemplateModule: pallet_template,
 		NFTCore: pallet_nft_core::{Pallet, Call, Event<T>, Storage},
 		Uniques: pallet_uniques::{Pallet, Call, Storage, Event<T>},
+		Marketplace: pallet_marketplace::{Pallet, Call, Storage, Event<T>},
+		Auction: pallet_auction::{Pallet, Call, Storage, Event<T>},
 	}
 );
 
@@ -505,6 +556,12 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_auction_runtime_api::AuctionApi<Block, u32, u32, Balance> for Runtime {
+		fn dutch_auction_price(collection_id: u32, nft_id: Option<u32>) -> Option<Balance> {
+			Auction::dutch_auction_price(collection_id, nft_id)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (
@@ -523,6 +580,7 @@ impl_runtime_apis! {
 			list_benchmark!(list, extra, pallet_balances, Balances);
 			list_benchmark!(list, extra, pallet_timestamp, Timestamp);
 			list_benchmark!(list, extra, pallet_template, TemplateModule);
+			list_benchmark!(list, extra, pallet_nft_core, NFTCore);
 
 			let storage_info = AllPalletsWithSystem::storage_info();
 
@@ -561,6 +619,7 @@ impl_runtime_apis! {
 			add_benchmark!(params, batches, pallet_balances, Balances);
 			add_benchmark!(params, batches, pallet_timestamp, Timestamp);
 			add_benchmark!(params, batches, pallet_template, TemplateModule);
+			add_benchmark!(params, batches, pallet_nft_core, NFTCore);
 
 			Ok(batches)
 		}